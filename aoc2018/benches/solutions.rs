@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::path::PathBuf;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+use common::{BoundingBox, Grid, PartNumber, Point, Solution};
+use generate::SyntheticRng;
+use read::Input;
+
+// ======================================================= CONSTANTS DEFINITIONS =======================================================
+
+const SAMPLE_SIZE : usize = 10;
+const SEED : u64 = 0x2018_1201;
+
+// ======================================================== AUXILIARY FUNCTIONS ========================================================
+
+fn checked_in_input(day: u8) -> Input {
+    let path : PathBuf = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join(format!("day_{:02}", day)).join("input.txt");
+    return Input::from_path(path).unwrap();
+}
+
+fn bench_part<S: Solution>(criterion_id: BenchmarkId, group: &mut criterion::BenchmarkGroup<criterion::measurement::WallTime>,
+    solution: &S, parsed: &S::Input, part: PartNumber) {
+
+    match part {
+        1 => group.bench_function(criterion_id, |bencher| bencher.iter(|| solution.part1(black_box(parsed)).unwrap())),
+        _ => group.bench_function(criterion_id, |bencher| bencher.iter(|| solution.part2(black_box(parsed)).unwrap())),
+    };
+}
+
+/// Benchmarks parsing and both parts of a day over its checked-in `input.txt`.
+fn bench_checked_in<S: Solution + Default>(criterion: &mut Criterion) {
+    let solution : S = S::default();
+    let input : Input = checked_in_input(S::DAY);
+    let parsed : S::Input = solution.parse(&input).unwrap();
+
+    let mut group = criterion.benchmark_group(format!("day_{:02}", S::DAY));
+    group.sample_size(SAMPLE_SIZE);
+    group.bench_function("parse", |bencher| bencher.iter(|| solution.parse(black_box(&input)).unwrap()));
+    for part in [1, 2] {
+        bench_part(BenchmarkId::from_parameter(format!("part{}", part)), &mut group, &solution, &parsed, part);
+    }
+    group.finish();
+}
+
+/// Benchmarks the given parts of a day over inputs built for every size, so that the scaling of the solvers can be compared.
+fn bench_scaled<S: Solution, F: Fn(usize) -> (S, Input)>(criterion: &mut Criterion, sizes: &[usize], parts: &[PartNumber], build: F) {
+    let mut group = criterion.benchmark_group(format!("synthetic/day_{:02}", S::DAY));
+    group.sample_size(SAMPLE_SIZE);
+    for &size in sizes {
+        let (solution, input) : (S, Input) = build(size);
+        let parsed : S::Input = solution.parse(&input).unwrap();
+        for &part in parts {
+            bench_part(BenchmarkId::new(format!("part{}", part), size), &mut group, &solution, &parsed, part);
+        }
+    }
+    group.finish();
+}
+
+fn synthetic_input(size: usize, text: String) -> Input {
+    return Input::from_string(&format!("synthetic-{}", size), &text);
+}
+
+// ========================================================= CHECKED-IN INPUTS =========================================================
+
+fn checked_in_inputs(criterion: &mut Criterion) {
+    bench_checked_in::<day_01::Day01>(criterion);
+    bench_checked_in::<day_02::Day02>(criterion);
+    bench_checked_in::<day_03::Day03>(criterion);
+    bench_checked_in::<day_04::Day04>(criterion);
+    bench_checked_in::<day_05::Day05>(criterion);
+    bench_checked_in::<day_06::Day06>(criterion);
+    bench_checked_in::<day_07::Day07>(criterion);
+    bench_checked_in::<day_08::Day08>(criterion);
+    bench_checked_in::<day_09::Day09>(criterion);
+    bench_checked_in::<day_10::Day10>(criterion);
+    bench_checked_in::<day_11::Day11>(criterion);
+    bench_checked_in::<day_12::Day12>(criterion);
+    bench_checked_in::<day_13::Day13>(criterion);
+    bench_checked_in::<day_14::Day14>(criterion);
+}
+
+// ========================================================= SYNTHETIC INPUTS ==========================================================
+
+fn synthetic_inputs(criterion: &mut Criterion) {
+
+    // Frequency changes summing up to zero, so that a repeated frequency is always found
+    bench_scaled(criterion, &[1_000, 10_000, 100_000], &[1, 2], |size| {
+        return (day_01::Day01, synthetic_input(size, generate::frequency_changes(&mut SyntheticRng::new(SEED), size)));
+    });
+
+    // Random box IDs, two of them differing by a single letter
+    bench_scaled(criterion, &[250, 1_000, 4_000], &[1, 2], |size| {
+        return (day_02::Day02::default(), synthetic_input(size, generate::box_ids(&mut SyntheticRng::new(SEED), size)));
+    });
+
+    // Random claims over the fabric, plus an isolated one
+    bench_scaled(criterion, &[500, 1_000, 2_000], &[1, 2], |size| {
+        return (day_03::Day03, synthetic_input(size, generate::claims(&mut SyntheticRng::new(SEED), size)));
+    });
+
+    // Random polymers mixing every unit type and polarity
+    bench_scaled(criterion, &[2_500, 5_000, 10_000], &[1, 2], |size| {
+        return (day_05::Day05, synthetic_input(size, generate::polymer(&mut SyntheticRng::new(SEED), size)));
+    });
+
+    // Random coordinates over an area similar to the puzzle input
+    bench_scaled(criterion, &[10, 25, 50], &[1, 2], |size| {
+        return (day_06::Day06::default(), synthetic_input(size, generate::coordinates(&mut SyntheticRng::new(SEED), size)));
+    });
+
+    // Increasingly large license trees
+    bench_scaled(criterion, &[1_000, 10_000, 100_000], &[1, 2], |size| {
+        return (day_08::Day08, synthetic_input(size, generate::license_tree(&mut SyntheticRng::new(SEED), size)));
+    });
+
+    // Increasingly long games (the second part plays a hundred times more marbles)
+    bench_scaled(criterion, &[1_000, 5_000, 25_000], &[1, 2], |size| {
+        return (day_09::Day09, synthetic_input(size, format!("400 players; last marble is worth {} points", size)));
+    });
+
+    // Messages of increasingly many letters, appearing after the same time
+    bench_scaled(criterion, &[4, 8, 16], &[2], |size| {
+        return (day_10::Day10::default(), synthetic_input(size, generate::point_cloud(&mut SyntheticRng::new(SEED), size, 10_000)));
+    });
+
+    // Increasingly large fuel grids
+    bench_scaled(criterion, &[50, 100, 200], &[1, 2], |size| {
+        let solution : day_11::Day11 = day_11::Day11 { grid_size: size as day_11::CoordinateUnit };
+        return (solution, synthetic_input(size, "18".to_owned()));
+    });
+
+    // Increasingly many track networks, each one with its own carts
+    bench_scaled(criterion, &[10, 50, 250], &[1, 2], |size| {
+        return (day_13::Day13, synthetic_input(size, generate::cart_tracks(&mut SyntheticRng::new(SEED), size)));
+    });
+
+    // Increasingly many recipes before the estimated scores
+    bench_scaled(criterion, &[10_000, 100_000, 1_000_000], &[1], |size| {
+        return (day_14::Day14::default(), synthetic_input(size, size.to_string()));
+    });
+}
+
+// ======================================================== GRIDS AND HASH MAPS ========================================================
+
+/// Closest coordinate of every point around them (`None` on ties), as mapped by day 6.
+fn closest_coordinate(coordinates: &[Point<i64>], point: &Point<i64>) -> Option<usize> {
+    let mut closest : (Option<usize>, i64) = (None, i64::MAX);
+    for (index, coordinate) in coordinates.iter().enumerate() {
+        let distance : i64 = coordinate.manhattan_distance(point);
+        if distance < closest.1 { closest = (Some(index), distance); } else if distance == closest.1 { closest.0 = None; }
+    }
+
+    return closest.0;
+}
+
+/// Builds the day 3 and day 6 mappings over the same synthetic inputs, once in the dense `Grid` the days use and once in a
+/// `HashMap` keyed by position, to compare both storages.
+fn grid_and_hash_map_mappings(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("mapping/day_03");
+    group.sample_size(SAMPLE_SIZE);
+    for size in [500, 1_000, 2_000] {
+        let areas : Vec<BoundingBox<i64>> = generate::claims(&mut SyntheticRng::new(SEED), size).lines()
+            .map(|line| day_03::Claim::new(line.to_owned()).unwrap().get_claimed_area())
+            .collect();
+        let limits : BoundingBox<i64> = BoundingBox::from_points(areas.iter().flat_map(|area| [&area.min, &area.max])).unwrap();
+        group.bench_function(BenchmarkId::new("grid", size), |bencher| bencher.iter(|| {
+            let mut counts : Grid<u32> = Grid::new(limits, 0);
+            for point in areas.iter().flat_map(|area| area.points()) { counts[point] = counts[point] + 1; }
+            return counts;
+        }));
+        group.bench_function(BenchmarkId::new("hash_map", size), |bencher| bencher.iter(|| {
+            let mut counts : HashMap<Point<i64>, u32> = HashMap::new();
+            for point in areas.iter().flat_map(|area| area.points()) {
+                let count : &mut u32 = counts.entry(point).or_insert(0);
+                *count = *count + 1;
+            }
+            return counts;
+        }));
+    }
+    group.finish();
+
+    let mut group = criterion.benchmark_group("mapping/day_06");
+    group.sample_size(SAMPLE_SIZE);
+    for size in [10, 25, 50] {
+        let coordinates : Vec<Point<i64>> = generate::coordinates(&mut SyntheticRng::new(SEED), size).lines()
+            .map(|line| day_06::parse_coordinate(line).unwrap())
+            .collect();
+        let limits : BoundingBox<i64> = BoundingBox::from_points(&coordinates).unwrap().expand(5);
+        group.bench_function(BenchmarkId::new("grid", size), |bencher| bencher.iter(|| {
+            let mut closest : Grid<Option<usize>> = Grid::new(limits, None);
+            for point in limits.points() { closest[point] = closest_coordinate(&coordinates, &point); }
+            return closest;
+        }));
+        group.bench_function(BenchmarkId::new("hash_map", size), |bencher| bencher.iter(|| {
+            return limits.points()
+                .map(|point| (point, closest_coordinate(&coordinates, &point)))
+                .collect::<HashMap<Point<i64>, Option<usize>>>();
+        }));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, checked_in_inputs, synthetic_inputs, grid_and_hash_map_mappings);
+criterion_main!(benches);
\ No newline at end of file
@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use common::{DayNumber, PartNumber};
+
+// ======================================================== STRUCTS DEFINITIONS ========================================================
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DayAnswers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+/// Registry of the known answers for the checked-in inputs, stored as `[day_NN]` tables with `part1`/`part2` strings.
+pub struct KnownAnswers {
+    days: HashMap<String, DayAnswers>,
+}
+
+// ====================================================== STRUCTS IMPLEMENTATIONS ======================================================
+
+impl KnownAnswers {
+    pub fn from_path(path: &Path) -> Result<KnownAnswers, String> {
+        let text : String = fs::read_to_string(path)
+            .map_err(|error| format!("Could not read answers '{}': {}", path.display(), error))?;
+        let days : HashMap<String, DayAnswers> = toml::from_str(&text)
+            .map_err(|error| format!("Could not parse answers '{}': {}", path.display(), error))?;
+
+        return Ok(KnownAnswers { days: days });
+    }
+
+    pub fn get(&self, day: DayNumber, part: PartNumber) -> Option<&str> {
+        let day_answers : &DayAnswers = self.days.get(&format!("day_{:02}", day))?;
+        return match part {
+            1 => day_answers.part1.as_deref(),
+            _ => day_answers.part2.as_deref(),
+        };
+    }
+}
\ No newline at end of file
@@ -0,0 +1,102 @@
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+
+use common::{DayNumber, ExampleOutcome, PartNumber, ProfiledAnswers, Solution, SolutionConfig, SolutionResult, profile_solution, run_example, save_render};
+use read::Input;
+
+// ======================================================== STRUCTS DEFINITIONS ========================================================
+
+pub struct Day {
+    pub number: DayNumber,
+    pub title: &'static str,
+    solve: fn(&SolutionConfig, &Input, &[PartNumber]) -> SolutionResult<ProfiledAnswers>,
+    render: fn(&SolutionConfig, &Input, &Path) -> SolutionResult<Vec<PathBuf>>,
+    example: fn(usize) -> SolutionResult<Vec<ExampleOutcome>>,
+}
+
+// ======================================================= CONSTANTS DEFINITIONS =======================================================
+
+pub const DAYS : [Day; 14] = [
+    Day::new::<day_01::Day01>(),
+    Day::configurable::<day_02::Day02>(),
+    Day::new::<day_03::Day03>(),
+    Day::new::<day_04::Day04>(),
+    Day::new::<day_05::Day05>(),
+    Day::configurable::<day_06::Day06>(),
+    Day::configurable::<day_07::Day07>(),
+    Day::new::<day_08::Day08>(),
+    Day::new::<day_09::Day09>(),
+    Day::configurable::<day_10::Day10>(),
+    Day::configurable::<day_11::Day11>(),
+    Day::configurable::<day_12::Day12>(),
+    Day::new::<day_13::Day13>(),
+    Day::configurable::<day_14::Day14>(),
+];
+
+// ======================================================== AUXILIARY FUNCTIONS ========================================================
+
+/// Solution of a day without tunable constants, refusing any configuration rather than silently ignoring it.
+fn unconfigured<S: Solution + Default>(config: &SolutionConfig) -> SolutionResult<S> {
+    if !config.is_empty(S::DAY) { return Err(format!("'{}' has no tunable constants", S::TITLE).into()); }
+    return Ok(S::default());
+}
+
+fn configured<S: Solution + Default + DeserializeOwned>(config: &SolutionConfig) -> SolutionResult<S> {
+    return Ok(config.solution::<S>()?);
+}
+
+fn solve_with<S: Solution>(solution: SolutionResult<S>, input: &Input, parts: &[PartNumber]) -> SolutionResult<ProfiledAnswers> {
+    return profile_solution(&solution?, input, parts);
+}
+
+fn render_with<S: Solution>(solution: SolutionResult<S>, input: &Input, path: &Path) -> SolutionResult<Vec<PathBuf>> {
+    return save_render(&solution?, input, path);
+}
+
+// ====================================================== STRUCTS IMPLEMENTATIONS ======================================================
+
+impl Day {
+    const fn new<S: Solution + Default>() -> Day {
+        Day {
+            number: S::DAY,
+            title: S::TITLE,
+            solve: |config, input, parts| solve_with(unconfigured::<S>(config), input, parts),
+            render: |config, input, path| render_with(unconfigured::<S>(config), input, path),
+            example: run_example::<S>,
+        }
+    }
+
+    const fn configurable<S: Solution + Default + DeserializeOwned>() -> Day {
+        Day {
+            number: S::DAY,
+            title: S::TITLE,
+            solve: |config, input, parts| solve_with(configured::<S>(config), input, parts),
+            render: |config, input, path| render_with(configured::<S>(config), input, path),
+            example: run_example::<S>,
+        }
+    }
+
+    pub fn get(number: DayNumber) -> Option<&'static Day> {
+        return DAYS.iter().find(|day| day.number == number);
+    }
+
+    pub fn default_input(&self) -> PathBuf {
+        return PathBuf::from(format!("day_{:02}", self.number)).join("input.txt");
+    }
+
+    /// Answers of the requested parts, along with the stats of the parsing and of every part.
+    pub fn solve(&self, config: &SolutionConfig, input: &Input, parts: &[PartNumber]) -> SolutionResult<ProfiledAnswers> {
+        return (self.solve)(config, input, parts);
+    }
+
+    /// Solves the `number`-th example of the puzzle statement (from 1), with the constants of the example.
+    pub fn run_example(&self, number: usize) -> SolutionResult<Vec<ExampleOutcome>> {
+        return (self.example)(number);
+    }
+
+    /// Writes the visualization of the input to `path`, returning the paths of the written frames.
+    pub fn render(&self, config: &SolutionConfig, input: &Input, path: &Path) -> SolutionResult<Vec<PathBuf>> {
+        return (self.render)(config, input, path);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,149 @@
+use regex::Regex;
+
+use common::DayNumber;
+
+// ======================================================== STRUCTS DEFINITIONS ========================================================
+
+/// Day an input may belong to, with a confidence from 0 to 1: how well the input fits the format of the day, shared with the other
+/// days whose format it fits as well (a single number is as much a day 11 serial number as a day 14 number of recipes).
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Detection {
+    pub day: DayNumber,
+    pub confidence: f64,
+}
+
+// ======================================================= CONSTANTS DEFINITIONS =======================================================
+
+/// Confidence under which an input is not dispatched to any day.
+pub const MIN_CONFIDENCE : f64 = 0.5;
+
+/// Formats of the days that are a list of similar lines, as the regex every line must match.
+const LINE_FORMATS : [(DayNumber, &str); 6] = [
+    (2, r"^[a-z]+$"),
+    (3, r"^#\d+ @ \d+,\d+: \d+x\d+$"),
+    (4, r"^\[\d{4}-\d{2}-\d{2} \d{2}:\d{2}\] (Guard #\d+ begins shift|falls asleep|wakes up)$"),
+    (6, r"^-?\d+, -?\d+$"),
+    (7, r"^Step [A-Z] must be finished before step [A-Z] can begin\.$"),
+    (10, r"^position=<\s*-?\d+,\s*-?\d+> velocity=<\s*-?\d+,\s*-?\d+>$"),
+];
+
+/// Formats of the days whose input is a single line.
+const SINGLE_LINE_FORMATS : [(DayNumber, &str); 3] = [
+    (5, r"^[a-zA-Z]+$"),
+    (8, r"^\d+( \d+)+$"),
+    (9, r"^\d+ players; last marble is worth \d+ points$"),
+];
+
+/// Day 1 changes, separated by new lines or commas, once their `#` comments are stripped.
+const FREQUENCY_CHANGES_FORMAT : &str = r"^[+-]\d+(,\s*[+-]\d+)*,?$";
+
+const TRACK_CHARACTERS : &str = " |-/\\+";
+const CART_CHARACTERS : &str = "<>^v";
+
+// ======================================================== AUXILIARY FUNCTIONS ========================================================
+
+fn matching_share(lines: &[&str], pattern: &str) -> f64 {
+    let regex : Regex = Regex::new(pattern).unwrap();
+    return lines.iter().filter(|line| regex.is_match(line)).count() as f64 / lines.len() as f64;
+}
+
+/// Day 11 serial numbers have four digits while day 14 numbers of recipes have six (and may start with a zero), but both are numbers.
+fn single_number_scores(line: &str) -> [(DayNumber, f64); 2] {
+    if line.is_empty() || !line.bytes().all(|byte| byte.is_ascii_digit()) { return [(11, 0.0), (14, 0.0)]; }
+    let looks_like_recipes : bool = line.len() > 4 || line.starts_with('0');
+    return if looks_like_recipes { [(11, 0.25), (14, 1.0)] } else { [(11, 1.0), (14, 0.25)] };
+}
+
+/// Day 1 inputs may hold comments and blank lines, which are skipped as the day 1 parser does before matching the changes.
+fn frequency_changes_score(lines: &[&str]) -> f64 {
+    let content_lines : Vec<&str> = lines.iter()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|content| !content.is_empty())
+        .collect();
+    if content_lines.is_empty() { return 0.0; }
+
+    return matching_share(&content_lines, FREQUENCY_CHANGES_FORMAT);
+}
+
+fn plants_score(lines: &[&str]) -> f64 {
+    let initial_state_regex : Regex = Regex::new(r"^initial state: [#.]+$").unwrap();
+    if !initial_state_regex.is_match(lines[0]) { return 0.0; }
+    if lines.len() == 1 { return 0.5; }
+
+    return 0.5 + 0.5 * matching_share(&lines[1..], r"^[#.]{5} => [#.]$");
+}
+
+/// Share of the lines drawn with track pieces only, halved when no cart is on them.
+fn tracks_score(text: &str) -> f64 {
+    let lines : Vec<&str> = text.lines().filter(|line| !line.trim().is_empty()).collect();
+    if lines.is_empty() { return 0.0; }
+
+    let is_track_line = |line: &&&str| line.chars().all(|character| TRACK_CHARACTERS.contains(character) || CART_CHARACTERS.contains(character));
+    let track_share : f64 = lines.iter().filter(is_track_line).count() as f64 / lines.len() as f64;
+    return if text.chars().any(|character| CART_CHARACTERS.contains(character)) { track_share } else { track_share / 2.0 };
+}
+
+/// Days the text may be the input of, from the most to the least likely, leaving out the days whose format it does not fit at all.
+pub fn detect_day(text: &str) -> Vec<Detection> {
+    let lines : Vec<&str> = text.lines().map(|line| line.trim_end()).filter(|line| !line.is_empty()).collect();
+    if lines.is_empty() { return Vec::new(); }
+
+    let mut scores : Vec<(DayNumber, f64)> = LINE_FORMATS.iter()
+        .map(|&(day, pattern)| (day, matching_share(&lines, pattern)))
+        .collect();
+    scores.push((1, frequency_changes_score(&lines)));
+    if lines.len() == 1 {
+        scores.extend(SINGLE_LINE_FORMATS.iter().map(|&(day, pattern)| (day, matching_share(&lines, pattern))));
+        scores.extend(single_number_scores(lines[0]));
+    }
+    scores.push((12, plants_score(&lines)));
+    scores.push((13, tracks_score(text)));
+
+    let total_score : f64 = scores.iter().map(|&(_, score)| score).sum();
+    let mut detections : Vec<Detection> = scores.into_iter()
+        .filter(|&(_, score)| score > 0.0)
+        .map(|(day, score)| Detection { day: day, confidence: score * score / total_score })
+        .collect();
+    detections.sort_by(|detection_1, detection_2| detection_2.confidence.total_cmp(&detection_1.confidence).then(detection_1.day.cmp(&detection_2.day)));
+
+    return detections;
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detected_day(text: &str) -> Option<DayNumber> {
+        return detect_day(text).first().filter(|detection| detection.confidence >= MIN_CONFIDENCE).map(|detection| detection.day);
+    }
+
+    #[test]
+    fn recognizes_every_checked_in_input() {
+        for day in 1..=14 {
+            let text : String = std::fs::read_to_string(format!("{}/../day_{:02}/input.txt", env!("CARGO_MANIFEST_DIR"), day)).unwrap();
+            let detections : Vec<Detection> = detect_day(&text);
+            assert_eq!(detections[0].day, day, "day {} detected as {:?}", day, detections);
+            assert!(detections[0].confidence >= MIN_CONFIDENCE, "day {} detected as {:?}", day, detections);
+        }
+    }
+
+    #[test]
+    fn shares_confidence_between_ambiguous_days() {
+        let detections : Vec<Detection> = detect_day("abcdef");
+        assert_eq!(detections.iter().map(|detection| detection.day).collect::<Vec<DayNumber>>(), vec![2, 5]);
+        assert_eq!(detections[0].confidence, 0.5);
+
+        assert_eq!(detected_day("7857"), Some(11));
+        assert_eq!(detected_day("074501"), Some(14));
+        assert_eq!(detect_day("074501").len(), 2);
+    }
+
+    #[test]
+    fn rejects_unknown_formats() {
+        assert_eq!(detect_day(""), Vec::new());
+        assert_eq!(detected_day("hello world\nthis is not a puzzle input"), None);
+        assert_eq!(detected_day("+1\nWhat is this?\n3 apples"), None);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,460 @@
+mod answers;
+mod days;
+mod detect;
+mod pool;
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::thread;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use clap::{ArgAction, Args, Parser, Subcommand};
+use serde_json::{json, Value};
+
+use common::{CountingAllocator, DayNumber, ExampleOutcome, OutputFormat, PartAnswer, PartNumber, PhaseStats, ProfiledAnswers, SolutionConfig, format_answer, init_logging,
+    print_example_outcomes, print_stats};
+use generate::{default_size, generate_input};
+use read::{Input, ReadResult};
+
+use answers::KnownAnswers;
+use days::{Day, DAYS};
+use detect::{Detection, MIN_CONFIDENCE, detect_day};
+use pool::run_isolated;
+
+#[global_allocator]
+static ALLOCATOR : CountingAllocator = CountingAllocator;
+
+// ======================================================== STRUCTS DEFINITIONS ========================================================
+
+#[derive(Parser)]
+#[command(name = "aoc2018", about = "🎄 Runner for the Advent of Code 2018 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Log the state of the simulations to stderr: '-v' for a summary of every step, '-vv' for full state dumps
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a single day, or every day in sequence with '--all'
+    Run(RunArgs),
+    /// Solve every day over its checked-in input and compare the answers with the known ones
+    Verify(VerifyArgs),
+    /// Print a random input for a day, in the exact format of its puzzle input
+    Generate(GenerateArgs),
+    /// Tell which days an input file may belong to, from the most to the least likely
+    Detect(DetectArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Day to be solved, detected from the format of the input when only '--input' is given
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=14), required_unless_present_any = ["all", "input"])]
+    day: Option<DayNumber>,
+    /// Part to be solved, both parts are solved when omitted
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<PartNumber>,
+    /// Input file ('-' reads from stdin), defaults to 'day_NN/input.txt' under the current directory
+    #[arg(long, conflicts_with = "all")]
+    input: Option<PathBuf>,
+    /// Solve every day in sequence and print a summary table
+    #[arg(long, conflicts_with = "day")]
+    all: bool,
+    /// With '--all', solve the days concurrently on N threads (as many as the CPUs when N is omitted)
+    #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "0", conflicts_with = "stats")]
+    parallel: Option<usize>,
+    /// Output format: 'text' prints the answers for humans, 'json' prints an array of results for scripts
+    #[arg(long, default_value = "text")]
+    format: OutputFormat,
+    /// Also write a visualization of the input (days 3, 6, 11 and 13), as PPM, PNG or SVG depending on the extension
+    #[arg(long, value_name = "PATH", conflicts_with = "all")]
+    render: Option<PathBuf>,
+    /// Solve the N-th example of the puzzle statement (from 1) instead of the input, comparing the answers with the expected ones
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(usize), conflicts_with_all = ["all", "input", "render", "config", "set"])]
+    example: Option<usize>,
+    /// TOML file whose '[day_NN]' tables override the puzzle constants (e.g. 'threshold' for day 6)
+    #[arg(long, value_name = "PATH")]
+    config: Option<PathBuf>,
+    /// Also print the wall time and heap peak of the parsing and of every part, as a table after the answers
+    #[arg(long, conflicts_with = "example")]
+    stats: bool,
+    /// Override a puzzle constant of the solved day, e.g. '--set number_workers=2' (repeatable, applied after '--config')
+    #[arg(long, value_name = "KEY=VALUE", conflicts_with = "all")]
+    set: Vec<String>,
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    /// Day to be verified, every day is verified when omitted
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=14))]
+    day: Option<DayNumber>,
+    /// Registry of the known answers
+    #[arg(long, default_value = "answers.toml")]
+    answers: PathBuf,
+}
+
+#[derive(Args)]
+struct GenerateArgs {
+    /// Day whose input is generated
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=14))]
+    day: DayNumber,
+    /// Size of the input (lines, units, nodes, letters, ... depending on the day), close to the puzzle input when omitted
+    #[arg(long)]
+    size: Option<usize>,
+    /// Seed of the generator, the same seed and size always give the same input
+    #[arg(long, default_value_t = 1)]
+    seed: u64,
+}
+
+#[derive(Args)]
+struct DetectArgs {
+    /// Input file ('-' reads from stdin)
+    #[arg(long)]
+    input: PathBuf,
+}
+
+struct PartResult {
+    day: DayNumber,
+    part_answer: PartAnswer,
+}
+
+struct DayResult {
+    parts: Vec<PartResult>,
+    stats: Vec<PhaseStats>,
+}
+
+/// Reason a day has no answers: an error reported by its solution, or a panic caught before it could take the other days down.
+enum DayFailure {
+    Error(String),
+    Panic(String),
+}
+
+type DayOutcome = Result<DayResult, DayFailure>;
+
+// ====================================================== STRUCTS IMPLEMENTATIONS ======================================================
+
+impl DayFailure {
+    fn status(&self) -> &'static str {
+        return match self {
+            DayFailure::Error(_) => "failed",
+            DayFailure::Panic(_) => "panicked",
+        };
+    }
+}
+
+impl fmt::Display for DayFailure {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DayFailure::Error(error) => write!(formatter, "{}", error),
+            DayFailure::Panic(message) => write!(formatter, "Panicked: {}", message),
+        }
+    }
+}
+
+// ======================================================== AUXILIARY FUNCTIONS ========================================================
+
+fn format_duration(duration: Duration) -> String {
+    return format!("{:.3} ms", duration.as_secs_f64() * 1000.0);
+}
+
+fn read_input(input_path: &PathBuf) -> ReadResult<Input> {
+    if input_path.as_os_str() == "-" { return Input::from_stdin(); }
+    return Input::from_path(input_path);
+}
+
+fn load_input(input_path: &PathBuf) -> Result<Input, String> {
+    return read_input(input_path).map_err(|error| format!("Could not read input: {}", error));
+}
+
+fn format_confidence(detection: &Detection) -> String {
+    return format!("day {:02} ({:.0}%)", detection.day, detection.confidence * 100.0);
+}
+
+/// Day of an input given without one, reporting the detection (and the other candidates, if any) on stderr.
+fn detect_input_day(input: &Input) -> Result<DayNumber, String> {
+    let detections : Vec<Detection> = detect_day(input.get_text());
+    let Some(best_detection) = detections.first().filter(|detection| detection.confidence >= MIN_CONFIDENCE) else {
+        let candidates : Vec<String> = detections.iter().map(format_confidence).collect();
+        return Err(format!("Could not tell which day '{}' belongs to, pass '--day' (candidates: {})", input.get_name(),
+            if candidates.is_empty() { "none".to_owned() } else { candidates.join(", ") }));
+    };
+
+    match detections.len() {
+        1 => eprintln!("🔎 Detected {}", format_confidence(best_detection)),
+        _ => eprintln!("🔎 Detected {}, could also be {}", format_confidence(best_detection),
+            detections[1..].iter().map(format_confidence).collect::<Vec<String>>().join(", ")),
+    }
+
+    return Ok(best_detection.day);
+}
+
+fn solve_day(day: &Day, config: &SolutionConfig, parts: &[PartNumber], input: &Input, render_path: Option<&PathBuf>) -> Result<DayResult, String> {
+    if let Some(render_path) = render_path {
+        let frame_paths : Vec<PathBuf> = day.render(config, input, render_path)
+            .map_err(|error| format!("Could not render '{}': {}", day.title, error))?;
+        frame_paths.iter().for_each(|frame_path| eprintln!("🖼️  Rendered '{}'", frame_path.display()));
+    }
+
+    let (answers, stats) : ProfiledAnswers = day.solve(config, input, parts)
+        .map_err(|error| format!("Could not solve '{}': {}", day.title, error))?;
+
+    let parts : Vec<PartResult> = answers.into_iter()
+        .map(|answer| PartResult { day: day.number, part_answer: answer })
+        .collect();
+    return Ok(DayResult { parts: parts, stats: stats });
+}
+
+/// Outcome of a day solved by a worker, which either returned (with answers or an error) or panicked.
+fn to_day_outcome(outcome: Result<Result<DayResult, String>, String>) -> DayOutcome {
+    return match outcome {
+        Ok(Ok(day_result)) => Ok(day_result),
+        Ok(Err(error)) => Err(DayFailure::Error(error)),
+        Err(message) => Err(DayFailure::Panic(message)),
+    };
+}
+
+fn print_result(result: &PartResult) {
+    let part_answer : &PartAnswer = &result.part_answer;
+    if part_answer.answer.contains('\n') {
+        println!("🎄 Day {:02} (Part {}) in {}:\n{}", result.day, part_answer.part, format_duration(part_answer.elapsed), part_answer.answer);
+    } else {
+        println!("🎄 Day {:02} (Part {}) in {}: '{}'", result.day, part_answer.part, format_duration(part_answer.elapsed), part_answer.answer);
+    }
+}
+
+/// Table of the answers of every day, with the status of the days that could not be solved, their times and the wall time of the run.
+fn print_summary(outcomes: &[(DayNumber, DayOutcome)], wall_time: Duration) {
+    let mut rows : Vec<(DayNumber, String, String, &str, Option<Duration>)> = Vec::new();
+    for (day, outcome) in outcomes.iter() {
+        match outcome {
+            Ok(day_result) => rows.extend(day_result.parts.iter()
+                .map(|result| (*day, result.part_answer.part.to_string(), result.part_answer.answer.clone(), "solved", Some(result.part_answer.elapsed)))),
+            Err(failure) => rows.push((*day, "-".to_owned(), failure.to_string(), failure.status(), None)),
+        }
+    }
+
+    let answer_width : usize = rows.iter()
+        .flat_map(|(_, _, answer, _, _)| answer.lines())
+        .map(|line| line.chars().count())
+        .chain(std::iter::once("Wall time".len()))
+        .max().unwrap();
+    let separator : String = format!("-----+------+-{}-+----------+-------------", "-".repeat(answer_width));
+
+    println!(" Day | Part | {:<width$} | Status   |         Time", "Answer", width = answer_width);
+    println!("{}", separator);
+    for (day, part, answer, status, elapsed) in rows.iter() {
+        let mut answer_lines = answer.lines();
+        let first_line : &str = answer_lines.next().unwrap_or("");
+        let elapsed : String = elapsed.map(format_duration).unwrap_or_default();
+        println!("  {:02} | {:>4} | {:<width$} | {:<8} | {:>12}", day, part, first_line, status, elapsed, width = answer_width);
+        for answer_line in answer_lines {
+            println!("     |      | {:<width$} |          |", answer_line, width = answer_width);
+        }
+    }
+
+    let total_elapsed : Duration = rows.iter().filter_map(|(_, _, _, _, elapsed)| *elapsed).sum();
+    println!("{}", separator);
+    println!("     |      | {:<width$} |          | {:>12}", "Total", format_duration(total_elapsed), width = answer_width);
+    println!("     |      | {:<width$} |          | {:>12}", "Wall time", format_duration(wall_time), width = answer_width);
+}
+
+fn print_json(outcomes: &[(DayNumber, DayOutcome)]) {
+    let mut entries : Vec<Value> = Vec::new();
+    for (day, outcome) in outcomes.iter() {
+        match outcome {
+            Ok(day_result) => entries.extend(day_result.parts.iter().map(|result| result.part_answer.to_json(result.day))),
+            Err(failure) => entries.push(json!({ "day": day, "status": failure.status(), "error": failure.to_string() })),
+        }
+    }
+
+    println!("{}", Value::Array(entries));
+}
+
+fn load_config(arguments: &RunArgs) -> Result<SolutionConfig, String> {
+    let mut config : SolutionConfig = match &arguments.config {
+        Some(config_path) => SolutionConfig::from_path(config_path)?,
+        None => SolutionConfig::default(),
+    };
+    for assignment in arguments.set.iter() { config.set(arguments.day.unwrap(), assignment)?; }
+
+    return Ok(config);
+}
+
+fn run_example(day: &Day, number: usize, parts: &[PartNumber], format: OutputFormat) -> ExitCode {
+    let outcomes : Vec<ExampleOutcome> = match day.run_example(number) {
+        Ok(outcomes) => outcomes.into_iter().filter(|outcome| parts.contains(&outcome.part_answer.part)).collect(),
+        Err(error) => { eprintln!("🚨 Could not solve '{}': {}", day.title, error); return ExitCode::FAILURE; },
+    };
+
+    let all_matched : bool = match format {
+        OutputFormat::Text => print_example_outcomes(day.number, number, &outcomes),
+        OutputFormat::Json => {
+            let entries : Vec<Value> = outcomes.iter()
+                .map(|outcome| {
+                    let mut entry : Value = outcome.part_answer.to_json(day.number);
+                    entry["example"] = json!(number);
+                    entry["expected"] = json!(outcome.expected);
+                    entry["matched"] = json!(outcome.matches());
+                    entry
+                })
+                .collect();
+            println!("{}", Value::Array(entries));
+            outcomes.iter().all(|outcome| outcome.matches())
+        },
+    };
+
+    return if all_matched { ExitCode::SUCCESS } else { ExitCode::FAILURE };
+}
+
+fn run(mut arguments: RunArgs) -> ExitCode {
+    if arguments.stats && arguments.format == OutputFormat::Json { eprintln!("🚨 '--stats' prints a table, it cannot be combined with JSON output"); return ExitCode::FAILURE; }
+    if arguments.parallel.is_some() && !arguments.all { eprintln!("🚨 '--parallel' only applies to '--all'"); return ExitCode::FAILURE; }
+
+    // The input of a detected day is read once, as it may come from stdin
+    let mut detected_input : Option<Input> = None;
+    if arguments.day.is_none() && !arguments.all {
+        let detection : Result<(Input, DayNumber), String> = load_input(arguments.input.as_ref().unwrap())
+            .and_then(|input| { let day : DayNumber = detect_input_day(&input)?; Ok((input, day)) });
+        match detection {
+            Ok((input, day)) => { arguments.day = Some(day); detected_input = Some(input); },
+            Err(error) => { eprintln!("🚨 {}", error); return ExitCode::FAILURE; },
+        }
+    }
+
+    let config : SolutionConfig = match load_config(&arguments) {
+        Ok(config) => config,
+        Err(error) => { eprintln!("🚨 {}", error); return ExitCode::FAILURE; },
+    };
+
+    let parts : Vec<PartNumber> = match arguments.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    if let Some(number) = arguments.example {
+        return run_example(Day::get(arguments.day.unwrap()).unwrap(), number, &parts, arguments.format);
+    }
+
+    let days : Vec<&Day> = match arguments.all {
+        true => DAYS.iter().collect(),
+        false => vec![Day::get(arguments.day.unwrap()).unwrap()],
+    };
+
+    let threads : usize = match arguments.parallel {
+        Some(0) => thread::available_parallelism().map(|threads| threads.get()).unwrap_or(1),
+        Some(threads) => threads,
+        None => 1,
+    };
+    let start : Instant = Instant::now();
+    let outcomes : Vec<(DayNumber, DayOutcome)> = run_isolated(days.len(), threads, |index| -> Result<DayResult, String> {
+        let input : Input = match &detected_input {
+            Some(input) => input.clone(),
+            None => load_input(&arguments.input.clone().unwrap_or_else(|| days[index].default_input()))?,
+        };
+        return solve_day(days[index], &config, &parts, &input, arguments.render.as_ref());
+    })
+        .into_iter().zip(days.iter())
+        .map(|(outcome, day)| (day.number, to_day_outcome(outcome)))
+        .collect();
+    let wall_time : Duration = start.elapsed();
+    let some_failed : bool = outcomes.iter().any(|(_, outcome)| outcome.is_err());
+
+    if arguments.format == OutputFormat::Json {
+        print_json(&outcomes);
+        return if some_failed { ExitCode::FAILURE } else { ExitCode::SUCCESS };
+    }
+
+    if arguments.all { print_summary(&outcomes, wall_time); }
+
+    let mut all_stats : Vec<(DayNumber, Vec<PhaseStats>)> = Vec::new();
+    for (day, outcome) in outcomes.into_iter() {
+        match outcome {
+            Ok(day_result) if arguments.all => all_stats.push((day, day_result.stats)),
+            Ok(day_result) => { day_result.parts.iter().for_each(print_result); all_stats.push((day, day_result.stats)); },
+            Err(_) if arguments.all => {},
+            Err(failure) => eprintln!("🚨 {}", failure),
+        }
+    }
+
+    if arguments.stats { println!(); print_stats(&all_stats); }
+    return if some_failed { ExitCode::FAILURE } else { ExitCode::SUCCESS };
+}
+
+fn verify(arguments: VerifyArgs) -> ExitCode {
+    let known_answers : KnownAnswers = match KnownAnswers::from_path(&arguments.answers) {
+        Ok(known_answers) => known_answers,
+        Err(error) => { eprintln!("🚨 {}", error); return ExitCode::FAILURE; },
+    };
+
+    let days : Vec<&Day> = match arguments.day {
+        Some(day) => vec![Day::get(day).unwrap()],
+        None => DAYS.iter().collect(),
+    };
+
+    let (mut matched, mut mismatched, mut unknown) : (usize, usize, usize) = (0, 0, 0);
+    for day in days.into_iter() {
+        let results : Vec<PartResult> = match load_input(&day.default_input()).and_then(|input| solve_day(day, &SolutionConfig::default(), &[1, 2], &input, None)) {
+            Ok(day_result) => day_result.parts,
+            Err(error) => { eprintln!("🚨 Day {:02}: {}", day.number, error); mismatched += 1; continue; },
+        };
+
+        for result in results.iter() {
+            let part_answer : &PartAnswer = &result.part_answer;
+            match known_answers.get(result.day, part_answer.part) {
+                Some(expected) if expected == part_answer.answer => {
+                    println!("✅ Day {:02} (Part {}): {}", result.day, part_answer.part, format_answer(&part_answer.answer));
+                    matched += 1;
+                },
+                Some(expected) => {
+                    println!("❌ Day {:02} (Part {}): expected {} but got {}", result.day, part_answer.part,
+                        format_answer(expected), format_answer(&part_answer.answer));
+                    mismatched += 1;
+                },
+                None => {
+                    println!("❔ Day {:02} (Part {}): no known answer, got {}", result.day, part_answer.part, format_answer(&part_answer.answer));
+                    unknown += 1;
+                },
+            }
+        }
+    }
+
+    println!("🎄 '{}' answers matched, '{}' mismatched and '{}' unknown", matched, mismatched, unknown);
+    return if mismatched > 0 { ExitCode::FAILURE } else { ExitCode::SUCCESS };
+}
+
+fn generate(arguments: GenerateArgs) -> ExitCode {
+    let size : usize = arguments.size.unwrap_or_else(|| default_size(arguments.day).unwrap());
+    println!("{}", generate_input(arguments.day, size, arguments.seed).unwrap());
+    return ExitCode::SUCCESS;
+}
+
+fn detect(arguments: DetectArgs) -> ExitCode {
+    let input : Input = match load_input(&arguments.input) {
+        Ok(input) => input,
+        Err(error) => { eprintln!("🚨 {}", error); return ExitCode::FAILURE; },
+    };
+
+    let detections : Vec<Detection> = detect_day(input.get_text());
+    if detections.is_empty() { eprintln!("🚨 '{}' does not look like the input of any day", input.get_name()); return ExitCode::FAILURE; }
+
+    for detection in detections.iter() {
+        let marker : &str = if detection.confidence >= MIN_CONFIDENCE { "🔎" } else { "❔" };
+        println!("{} Day {:02} '{}': {:.0}%", marker, detection.day, Day::get(detection.day).unwrap().title, detection.confidence * 100.0);
+    }
+
+    return ExitCode::SUCCESS;
+}
+
+fn main() -> ExitCode {
+    let cli : Cli = Cli::parse();
+    init_logging(cli.verbose);
+    match cli.command {
+        Command::Run(arguments) => run(arguments),
+        Command::Verify(arguments) => verify(arguments),
+        Command::Generate(arguments) => generate(arguments),
+        Command::Detect(arguments) => detect(arguments),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,63 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+// ======================================================= CONSTANTS DEFINITIONS =======================================================
+
+/// Stack of the workers, as large as the one of the main thread so that solving on a worker is no more fragile than in sequence.
+const WORKER_STACK_SIZE : usize = 8 * 1024 * 1024;
+
+// ======================================================== AUXILIARY FUNCTIONS ========================================================
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() { return message.to_string(); }
+    if let Some(message) = payload.downcast_ref::<String>() { return message.clone(); }
+    return "unknown panic".to_owned();
+}
+
+/// Runs `task` for every index of `0..count` on up to `threads` workers, each worker taking the next index as soon as it is free.
+/// Results are returned in the order of the indexes, a task that panicked giving its panic message instead of stopping the others.
+pub fn run_isolated<T: Send, F: Fn(usize) -> T + Sync>(count: usize, threads: usize, task: F) -> Vec<Result<T, String>> {
+    let next_index : AtomicUsize = AtomicUsize::new(0);
+    let results : Mutex<Vec<Option<Result<T, String>>>> = Mutex::new((0..count).map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for worker in 0..threads.clamp(1, count.max(1)) {
+            thread::Builder::new().name(format!("worker-{}", worker)).stack_size(WORKER_STACK_SIZE)
+                .spawn_scoped(scope, || loop {
+                    let index : usize = next_index.fetch_add(1, Ordering::Relaxed);
+                    if index >= count { break; }
+
+                    let result : Result<T, String> = panic::catch_unwind(AssertUnwindSafe(|| task(index))).map_err(panic_message);
+                    results.lock().unwrap()[index] = Some(result);
+                })
+                .expect("could not spawn a worker thread");
+        }
+    });
+
+    return results.into_inner().unwrap().into_iter()
+        .map(|result| result.expect("every task is run by a worker"))
+        .collect();
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_results_in_order() {
+        let results : Vec<Result<usize, String>> = run_isolated(20, 4, |index| index * index);
+        assert_eq!(results, (0..20).map(|index| Ok(index * index)).collect::<Vec<Result<usize, String>>>());
+    }
+
+    #[test]
+    fn isolates_panicking_tasks() {
+        let results : Vec<Result<usize, String>> = run_isolated(5, 2, |index| if index == 2 { panic!("broken task {}", index) } else { index });
+        assert_eq!(results[2], Err("broken task 2".to_owned()));
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 4);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,35 @@
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn run(arguments: &[&str]) -> Output {
+    return Command::new(env!("CARGO_BIN_EXE_aoc2018"))
+        .arg("run")
+        .args(arguments)
+        .current_dir(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(".."))
+        .output().unwrap();
+}
+
+#[test]
+fn keeps_answers_with_example_config() {
+    let output : Output = run(&["--day", "7", "--config", "config.example.toml"]);
+    let stdout : String = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success(), "unexpected output: {}", stdout);
+    assert!(stdout.contains("'IOFSJQDUWAPXELNVYZMHTBCRGK'") && stdout.contains("'931'"), "unexpected output: {}", stdout);
+}
+
+#[test]
+fn overrides_constants_from_flags() {
+    let output : Output = run(&["--day", "7", "--part", "2", "--config", "config.example.toml", "--set", "number_workers=1"]);
+    let stdout : String = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("'1911'"), "unexpected output: {}", stdout);
+}
+
+#[test]
+fn rejects_unknown_constants() {
+    let output : Output = run(&["--day", "6", "--set", "thresold=32"]);
+    let stderr : String = String::from_utf8(output.stderr).unwrap();
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("unknown field `thresold`"), "unexpected output: {}", stderr);
+}
\ No newline at end of file
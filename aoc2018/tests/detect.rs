@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn aoc2018(arguments: &[&str]) -> Output {
+    return Command::new(env!("CARGO_BIN_EXE_aoc2018"))
+        .args(arguments)
+        .current_dir(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(".."))
+        .output().unwrap();
+}
+
+#[test]
+fn solves_inputs_given_without_a_day() {
+    let output : Output = aoc2018(&["run", "--input", "day_09/input.txt", "--part", "1"]);
+    let (stdout, stderr) : (String, String) = (String::from_utf8(output.stdout).unwrap(), String::from_utf8(output.stderr).unwrap());
+
+    assert!(output.status.success(), "unexpected output: {}", stderr);
+    assert!(stderr.contains("Detected day 09 (100%)"), "unexpected output: {}", stderr);
+    assert!(stdout.contains("Day 09 (Part 1)"), "unexpected output: {}", stdout);
+}
+
+#[test]
+fn detects_commented_frequency_changes() {
+    let path : PathBuf = std::env::temp_dir().join(format!("aoc2018_commented_changes_{}.txt", std::process::id()));
+    std::fs::write(&path, "# calibration of device 7\n+1, -2,\n\n+3   # drift\n# end of the first batch\n+1\n").unwrap();
+    let output : Output = aoc2018(&["run", "--input", path.to_str().unwrap()]);
+    std::fs::remove_file(&path).unwrap();
+    let (stdout, stderr) : (String, String) = (String::from_utf8(output.stdout).unwrap(), String::from_utf8(output.stderr).unwrap());
+
+    assert!(output.status.success(), "unexpected output: {}", stderr);
+    assert!(stderr.contains("Detected day 01 (100%)"), "unexpected output: {}", stderr);
+    assert!(stdout.contains("'3'") && stdout.contains("'2'"), "unexpected output: {}", stdout);
+}
+
+#[test]
+fn lists_ambiguous_candidates() {
+    let output : Output = aoc2018(&["detect", "--input", "day_11/input.txt"]);
+    let stdout : String = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    let candidates : Vec<&str> = stdout.lines().collect();
+    assert!(candidates[0].starts_with("🔎 Day 11") && candidates[1].starts_with("❔ Day 14"), "unexpected output: {}", stdout);
+}
+
+#[test]
+fn refuses_unknown_inputs() {
+    let output : Output = aoc2018(&["run", "--input", "README.md"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("pass '--day'"));
+}
\ No newline at end of file
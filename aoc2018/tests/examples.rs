@@ -0,0 +1,26 @@
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn run_example(day: u8, number: usize) -> Output {
+    return Command::new(env!("CARGO_BIN_EXE_aoc2018"))
+        .args(["run", "--day", &day.to_string(), "--example", &number.to_string()])
+        .current_dir(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(".."))
+        .output().unwrap();
+}
+
+#[test]
+fn matches_every_embedded_example() {
+    for day in 1..=14 {
+        let mut number : usize = 1;
+        loop {
+            let output : Output = run_example(day, number);
+            let (stdout, stderr) : (String, String) = (String::from_utf8(output.stdout).unwrap(), String::from_utf8(output.stderr).unwrap());
+            if stderr.contains("there is no example") { break; }
+
+            assert!(output.status.success() && !stdout.contains('❌'), "day {} example {}: {}{}", day, number, stdout, stderr);
+            number = number + 1;
+        }
+
+        assert!(number > 1, "day {} has no examples", day);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,89 @@
+use common::{Solution, fuzz_parse, run_solution};
+use generate::{SyntheticRng, generate_input};
+use read::Input;
+
+const SEEDS : [u64; 5] = [1, 2, 3, 5, 8];
+
+fn solve<S: Solution>(solution: &S, input: String) -> Vec<String> {
+    return run_solution(solution, &Input::from_string("generated", &input), &[1, 2])
+        .unwrap_or_else(|error| panic!("day {} failed on a generated input: {}\n{}", S::DAY, error, input))
+        .into_iter().map(|part_answer| part_answer.answer)
+        .collect();
+}
+
+#[test]
+fn solves_generated_inputs_of_every_day() {
+    for seed in SEEDS {
+        let mut rng : SyntheticRng = SyntheticRng::new(seed);
+        solve(&day_01::Day01, generate::frequency_changes(&mut rng, 200));
+        solve(&day_02::Day02::default(), generate::box_ids(&mut rng, 100));
+        solve(&day_03::Day03, generate::claims(&mut rng, 200));
+        solve(&day_04::Day04, generate::guard_logs(&mut rng, 50));
+        solve(&day_05::Day05, generate::polymer(&mut rng, 500));
+        solve(&day_06::Day06::default(), generate::coordinates(&mut rng, 20));
+        solve(&day_07::Day07::default(), generate::step_dependencies(&mut rng, 10));
+        solve(&day_08::Day08, generate::license_tree(&mut rng, 200));
+        solve(&day_09::Day09, generate::marble_game(&mut rng, 500));
+        solve(&day_11::Day11 { grid_size: 50 }, generate::serial_number(&mut rng));
+        solve(&day_12::Day12::default(), generate::plant_rules(&mut rng, 30));
+        solve(&day_14::Day14::default(), generate::recipe_scores(&mut rng, 4));
+    }
+}
+
+#[test]
+fn finds_the_planted_answers() {
+    for seed in SEEDS {
+        let mut rng : SyntheticRng = SyntheticRng::new(seed);
+
+        let answers : Vec<String> = solve(&day_07::Day07::default(), generate::step_dependencies(&mut rng, 12));
+        assert_eq!(answers[0].len(), 12);
+
+        let seconds : usize = 50 + rng.index(100);
+        let answers : Vec<String> = solve(&day_10::Day10::default(), generate::point_cloud(&mut rng, 3, seconds));
+        assert_eq!(answers[0].lines().count(), 8);
+        assert_eq!(answers[1], seconds.to_string());
+
+        let answers : Vec<String> = solve(&day_13::Day13, generate::cart_tracks(&mut rng, 4));
+        assert_ne!(answers[0], answers[1]);
+    }
+}
+
+/// Damaged copy of the input: truncated, with a few bytes replaced or with a number inflated far beyond the puzzle sizes.
+fn mutate(rng: &mut SyntheticRng, input: &str) -> Vec<u8> {
+    let mut bytes : Vec<u8> = input.as_bytes().to_vec();
+    match rng.index(3) {
+        0 => bytes.truncate(rng.index(bytes.len() + 1)),
+        1 => for _ in 0..1 + rng.index(4) {
+            let index : usize = rng.index(bytes.len().max(1));
+            if index < bytes.len() { bytes[index] = rng.range(0, 256) as u8; }
+        },
+        _ => { let index : usize = rng.index(bytes.len() + 1); bytes.splice(index..index, b"99999999999999999999".iter().copied()); },
+    }
+
+    return bytes;
+}
+
+#[test]
+fn parses_mutated_inputs_without_panicking() {
+    let parsers : [fn(&[u8]); 14] = [fuzz_parse::<day_01::Day01>, fuzz_parse::<day_02::Day02>, fuzz_parse::<day_03::Day03>, fuzz_parse::<day_04::Day04>,
+        fuzz_parse::<day_05::Day05>, fuzz_parse::<day_06::Day06>, fuzz_parse::<day_07::Day07>, fuzz_parse::<day_08::Day08>, fuzz_parse::<day_09::Day09>,
+        fuzz_parse::<day_10::Day10>, fuzz_parse::<day_11::Day11>, fuzz_parse::<day_12::Day12>, fuzz_parse::<day_13::Day13>, fuzz_parse::<day_14::Day14>];
+
+    let mut rng : SyntheticRng = SyntheticRng::new(17);
+    for (day, parse) in (1..=14).zip(parsers) {
+        let input : String = generate_input(day, 12, rng.next_u64()).unwrap();
+        for _ in 0..100 {
+            parse(&mutate(&mut rng, &input));
+        }
+    }
+}
+
+#[test]
+fn repeats_inputs_for_the_same_seed() {
+    for day in 1..=14 {
+        assert_eq!(generate_input(day, 20, 7), generate_input(day, 20, 7));
+        assert_ne!(generate_input(day, 20, 7), generate_input(day, 20, 8), "day {} ignores the seed", day);
+    }
+
+    assert_eq!(generate_input(15, 20, 7), None);
+}
\ No newline at end of file
@@ -0,0 +1,42 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn run(arguments: &[&str]) -> Output {
+    return Command::new(env!("CARGO_BIN_EXE_aoc2018"))
+        .arg("run")
+        .args(arguments)
+        .current_dir(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(".."))
+        .output().unwrap();
+}
+
+#[test]
+fn solves_every_day_concurrently() {
+    let output : Output = run(&["--all", "--parallel", "4", "--part", "1"]);
+    let stdout : String = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "unexpected output: {}", stdout);
+
+    let solved_days : Vec<&str> = stdout.lines().filter(|line| line.contains("| solved ")).collect();
+    assert_eq!(solved_days.len(), 14, "unexpected output: {}", stdout);
+    assert!(solved_days[8].starts_with("  09 |    1 | 398502 "), "days should keep their order: {}", stdout);
+    assert!(stdout.contains("Wall time"), "unexpected output: {}", stdout);
+}
+
+#[test]
+fn reports_failed_days_in_the_table() {
+    let config_path : PathBuf = std::env::temp_dir().join(format!("aoc2018-parallel-{}.toml", std::process::id()));
+    fs::write(&config_path, "[day_11]\ngrid_size = 0\n").unwrap();
+    let output : Output = run(&["--all", "--parallel", "3", "--part", "1", "--config", config_path.to_str().unwrap()]);
+    let stdout : String = String::from_utf8(output.stdout).unwrap();
+    fs::remove_file(&config_path).unwrap();
+
+    assert!(!output.status.success());
+    assert!(stdout.lines().any(|line| line.starts_with("  11 |    - |") && line.contains("| failed ")), "unexpected output: {}", stdout);
+    assert_eq!(stdout.lines().filter(|line| line.contains("| solved ")).count(), 13, "unexpected output: {}", stdout);
+}
+
+#[test]
+fn rejects_parallel_runs_of_a_single_day() {
+    let output : Output = run(&["--day", "3", "--parallel", "2"]);
+    assert!(!output.status.success());
+}
\ No newline at end of file
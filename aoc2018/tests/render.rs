@@ -0,0 +1,32 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn render(day: &str, render_path: &PathBuf) -> Output {
+    return Command::new(env!("CARGO_BIN_EXE_aoc2018"))
+        .args(["run", "--day", day, "--part", "1", "--render", render_path.to_str().unwrap()])
+        .current_dir(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(".."))
+        .output().unwrap();
+}
+
+#[test]
+fn renders_grid_days() {
+    let render_path : PathBuf = std::env::temp_dir().join(format!("aoc2018-render-{}.png", std::process::id()));
+    let output : Output = render("6", &render_path);
+    let image : Vec<u8> = fs::read(&render_path).unwrap();
+    fs::remove_file(&render_path).unwrap();
+
+    assert!(output.status.success(), "unexpected output: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(image.starts_with(b"\x89PNG"));
+}
+
+#[test]
+fn rejects_days_without_visualization() {
+    let render_path : PathBuf = std::env::temp_dir().join(format!("aoc2018-render-{}-missing.png", std::process::id()));
+    let output : Output = render("1", &render_path);
+    let stderr : String = String::from_utf8(output.stderr).unwrap();
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("has no visualization"), "unexpected output: {}", stderr);
+    assert!(!render_path.exists());
+}
\ No newline at end of file
@@ -0,0 +1,28 @@
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn run(arguments: &[&str]) -> Output {
+    return Command::new(env!("CARGO_BIN_EXE_aoc2018"))
+        .arg("run")
+        .args(arguments)
+        .current_dir(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(".."))
+        .output().unwrap();
+}
+
+#[test]
+fn reports_every_phase() {
+    let output : Output = run(&["--day", "8", "--stats"]);
+    let stdout : String = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "unexpected output: {}", stdout);
+
+    let phase_rows : Vec<&str> = stdout.lines().filter(|line| line.starts_with("  08 |")).collect();
+    assert_eq!(phase_rows.len(), 3, "unexpected output: {}", stdout);
+    assert!(phase_rows[0].contains("parse") && phase_rows[0].contains("KiB"), "parsing the tree should allocate: {}", stdout);
+    assert!(phase_rows[2].contains("part 2"), "unexpected output: {}", stdout);
+}
+
+#[test]
+fn rejects_stats_in_json() {
+    let output : Output = run(&["--day", "8", "--stats", "--format", "json"]);
+    assert!(!output.status.success());
+}
\ No newline at end of file
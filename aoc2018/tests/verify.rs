@@ -0,0 +1,33 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn verify(arguments: &[&str]) -> Output {
+    return Command::new(env!("CARGO_BIN_EXE_aoc2018"))
+        .arg("verify")
+        .args(arguments)
+        .current_dir(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(".."))
+        .output().unwrap();
+}
+
+#[test]
+fn matches_known_answers() {
+    let output : Output = verify(&["--day", "1"]);
+    let stdout : String = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success(), "unexpected output: {}", stdout);
+    assert!(stdout.contains("'2' answers matched, '0' mismatched and '0' unknown"), "unexpected output: {}", stdout);
+}
+
+#[test]
+fn flags_mismatched_answers() {
+    let answers_path : PathBuf = std::env::temp_dir().join(format!("aoc2018-answers-{}.toml", std::process::id()));
+    fs::write(&answers_path, "[day_01]\npart1 = \"530\"\n").unwrap();
+    let output : Output = verify(&["--day", "1", "--answers", answers_path.to_str().unwrap()]);
+    let stdout : String = String::from_utf8(output.stdout).unwrap();
+    fs::remove_file(&answers_path).unwrap();
+
+    assert!(!output.status.success());
+    assert!(stdout.contains("expected '530' but got '531'"), "unexpected output: {}", stdout);
+    assert!(stdout.contains("'0' answers matched, '1' mismatched and '1' unknown"), "unexpected output: {}", stdout);
+}
\ No newline at end of file
@@ -0,0 +1,137 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use toml::{Table, Value};
+
+use crate::output::{flag_value, flag_values};
+use crate::solution::{DayNumber, Solution};
+
+// ======================================================== STRUCTS DEFINITIONS ========================================================
+
+/// Tunable puzzle constants, stored as one `[day_NN]` table per day with the fields of its solution struct.
+#[derive(Clone, Default, Debug)]
+pub struct SolutionConfig {
+    days: Table,
+}
+
+// ======================================================== AUXILIARY FUNCTIONS ========================================================
+
+fn day_key(day: DayNumber) -> String {
+    return format!("day_{:02}", day);
+}
+
+/// Value of a `key=value` assignment, parsed as TOML (so `0.5`, `[3, 7]` or `"text"`), or taken verbatim as a string.
+fn parse_assignment(assignment: &str) -> Result<(String, Value), String> {
+    let (key, raw_value) : (&str, &str) = assignment.split_once('=')
+        .ok_or(format!("assignment '{}' is not of the form 'key=value'", assignment))?;
+    let value : Value = toml::from_str::<Table>(&format!("value = {}", raw_value.trim())).ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| Value::String(raw_value.trim().to_owned()));
+
+    return Ok((key.trim().to_owned(), value));
+}
+
+/// Solution of the day configured from the `--config <path>` and `--set <key=value>` flags of the process arguments.
+pub fn solution_from_args<S: Solution + Default + DeserializeOwned>() -> Result<S, String> {
+    let mut config : SolutionConfig = match flag_value("config")? {
+        Some(path) => SolutionConfig::from_path(&PathBuf::from(path))?,
+        None => SolutionConfig::default(),
+    };
+    for assignment in flag_values("set")?.iter() { config.set(S::DAY, assignment)?; }
+
+    return config.solution();
+}
+
+// ====================================================== STRUCTS IMPLEMENTATIONS ======================================================
+
+impl SolutionConfig {
+    pub fn from_path(path: &Path) -> Result<SolutionConfig, String> {
+        let text : String = fs::read_to_string(path)
+            .map_err(|error| format!("Could not read config '{}': {}", path.display(), error))?;
+        return SolutionConfig::parse(&text).map_err(|error| format!("Could not parse config '{}': {}", path.display(), error));
+    }
+
+    pub fn parse(text: &str) -> Result<SolutionConfig, String> {
+        let days : Table = toml::from_str(text).map_err(|error| error.to_string())?;
+        if let Some((key, _)) = days.iter().find(|(_, value)| !value.is_table()) {
+            return Err(format!("'{}' should be a '[day_NN]' table", key));
+        }
+
+        return Ok(SolutionConfig { days: days });
+    }
+
+    /// Overrides a constant of the day from a `key=value` assignment.
+    pub fn set(&mut self, day: DayNumber, assignment: &str) -> Result<(), String> {
+        let (key, value) : (String, Value) = parse_assignment(assignment)?;
+        let day_table : &mut Value = self.days.entry(day_key(day)).or_insert_with(|| Value::Table(Table::new()));
+        day_table.as_table_mut().unwrap().insert(key, value);
+        return Ok(());
+    }
+
+    pub fn is_empty(&self, day: DayNumber) -> bool {
+        return self.days.get(&day_key(day)).and_then(|day_table| day_table.as_table()).is_none_or(|day_table| day_table.is_empty());
+    }
+
+    /// Solution of the day with the configured constants, the missing ones keeping their default value.
+    pub fn solution<S: Solution + Default + DeserializeOwned>(&self) -> Result<S, String> {
+        return match self.days.get(&day_key(S::DAY)) {
+            Some(day_table) => S::deserialize(day_table.clone())
+                .map_err(|error| format!("Invalid config for day {:02}: {}", S::DAY, error.to_string().trim_end().replace('\n', " "))),
+            None => Ok(S::default()),
+        };
+    }
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use read::Input;
+    use serde::Deserialize;
+
+    #[derive(Default, Debug, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    struct Tunable {
+        workers: usize,
+        factor: f64,
+        seeds: Vec<u8>,
+    }
+
+    impl Solution for Tunable {
+        const DAY : DayNumber = 1;
+        const TITLE : &'static str = "Tunable";
+
+        type Input = ();
+        type Part1 = usize;
+        type Part2 = usize;
+        type Error = std::io::Error;
+
+        fn parse(&self, _input: &Input) -> Result<(), std::io::Error> { Ok(()) }
+        fn part1(&self, _input: &()) -> Result<usize, std::io::Error> { Ok(self.workers) }
+        fn part2(&self, _input: &()) -> Result<usize, std::io::Error> { Ok(self.seeds.len()) }
+    }
+
+    #[test]
+    fn overrides_configured_constants() {
+        let mut config : SolutionConfig = SolutionConfig::parse("[day_01]\nworkers = 2\nfactor = 0.5\n").unwrap();
+        config.set(1, "seeds=[3, 7, 9]").unwrap();
+        config.set(1, "workers = 4").unwrap();
+
+        let tunable : Tunable = config.solution().unwrap();
+        assert_eq!((tunable.workers, tunable.factor, tunable.seeds), (4, 0.5, vec![3, 7, 9]));
+        assert!(config.is_empty(2));
+    }
+
+    #[test]
+    fn rejects_invalid_constants() {
+        assert!(SolutionConfig::parse("workers = 2").is_err());
+        assert!(SolutionConfig::default().set(1, "workers").is_err());
+
+        let mut config : SolutionConfig = SolutionConfig::default();
+        config.set(1, "wokers=2").unwrap();
+        assert!(config.solution::<Tunable>().unwrap_err().contains("unknown field `wokers`"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,88 @@
+use read::Input;
+
+use crate::output::{flag_value, format_answer};
+use crate::solution::{DayNumber, PartAnswer, PartNumber, Solution, SolutionResult, run_solution};
+
+// ======================================================== STRUCTS DEFINITIONS ========================================================
+
+/// Example of the puzzle statement, solved with its own constants when they differ from the real puzzle (e.g. fewer workers).
+pub struct Example<S> {
+    pub input: &'static str,
+    pub solution: S,
+    pub part1: Option<&'static str>,
+    pub part2: Option<&'static str>,
+}
+
+pub struct ExampleOutcome {
+    pub part_answer: PartAnswer,
+    pub expected: &'static str,
+}
+
+// ======================================================== AUXILIARY FUNCTIONS ========================================================
+
+/// Reads the `--example <N>` flag of the process arguments, examples being numbered from 1.
+pub fn example_from_args() -> Result<Option<usize>, String> {
+    return match flag_value("example")? {
+        Some(number) => number.parse().map(Some).map_err(|_| format!("invalid example number '{}'", number)),
+        None => Ok(None),
+    };
+}
+
+/// Solves the parts of the `number`-th example that have an expected answer.
+pub fn run_example<S: Solution>(number: usize) -> SolutionResult<Vec<ExampleOutcome>> {
+    let examples : Vec<Example<S>> = S::examples();
+    let example : &Example<S> = number.checked_sub(1).and_then(|index| examples.get(index))
+        .ok_or(format!("'{}' has '{}' examples, there is no example '{}'", S::TITLE, examples.len(), number))?;
+
+    let expected_answers : Vec<(PartNumber, &'static str)> = [(1, example.part1), (2, example.part2)].into_iter()
+        .filter_map(|(part, expected)| Some((part, expected?)))
+        .collect();
+    let parts : Vec<PartNumber> = expected_answers.iter().map(|&(part, _)| part).collect();
+    let part_answers : Vec<PartAnswer> = run_solution(&example.solution, &Input::from_string(&format!("example {}", number), example.input), &parts)?;
+
+    return Ok(part_answers.into_iter().zip(expected_answers)
+        .map(|(part_answer, (_, expected))| ExampleOutcome { part_answer: part_answer, expected: expected })
+        .collect());
+}
+
+/// Prints every outcome next to its expected answer, returning whether all of them matched.
+pub fn print_example_outcomes(day: DayNumber, number: usize, outcomes: &[ExampleOutcome]) -> bool {
+    for outcome in outcomes.iter() {
+        let part_answer : &PartAnswer = &outcome.part_answer;
+        if outcome.matches() {
+            println!("✅ Day {:02} (Part {}) on example {}: {}", day, part_answer.part, number, format_answer(&part_answer.answer));
+        } else {
+            println!("❌ Day {:02} (Part {}) on example {}: expected {} but got {}", day, part_answer.part, number,
+                format_answer(outcome.expected), format_answer(&part_answer.answer));
+        }
+    }
+
+    return outcomes.iter().all(|outcome| outcome.matches());
+}
+
+/// Solves the `number`-th example and prints how it compares with the expected answers, as the `--example` mode of the day binaries.
+pub fn check_example<S: Solution>(number: usize) -> SolutionResult<bool> {
+    return Ok(print_example_outcomes(S::DAY, number, &run_example::<S>(number)?));
+}
+
+// ====================================================== STRUCTS IMPLEMENTATIONS ======================================================
+
+impl<S> Example<S> {
+    pub fn new(input: &'static str, solution: S) -> Example<S> {
+        Example { input: input, solution: solution, part1: None, part2: None }
+    }
+
+    pub fn part1(self, expected: &'static str) -> Example<S> {
+        return Example { part1: Some(expected), ..self };
+    }
+
+    pub fn part2(self, expected: &'static str) -> Example<S> {
+        return Example { part2: Some(expected), ..self };
+    }
+}
+
+impl ExampleOutcome {
+    pub fn matches(&self) -> bool {
+        return self.part_answer.answer == self.expected;
+    }
+}
\ No newline at end of file
@@ -0,0 +1,248 @@
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use serde::Serialize;
+
+// ======================================================== TRAITS DEFINITIONS =========================================================
+
+/// Integer type usable as the unit of a `Point`, signed or not.
+pub trait Coordinate: Copy + Ord + Hash + Debug + Display + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+
+    /// Conversions used to index dense storage, only meant for small non-negative offsets.
+    fn to_usize(self) -> usize;
+    fn from_usize(value: usize) -> Self;
+}
+
+// ======================================================== STRUCTS DEFINITIONS ========================================================
+
+/// Point of a 2D grid, where `y` grows downwards: points are ordered in reading order (top to bottom, then left to right).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Serialize)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// Inclusive rectangle between its top left (`min`) and bottom right (`max`) corners.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct BoundingBox<T> {
+    pub min: Point<T>,
+    pub max: Point<T>,
+}
+
+// ======================================================= CONSTANTS DEFINITIONS =======================================================
+
+const ORTHOGONAL_DIRECTIONS : [(i8, i8); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const ALL_DIRECTIONS : [(i8, i8); 8] = [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+// ====================================================== TRAITS IMPLEMENTATIONS =======================================================
+
+macro_rules! impl_coordinate {
+    ($($unit:ty),*) => {
+        $(
+            impl Coordinate for $unit {
+                const ZERO : $unit = 0;
+                const ONE : $unit = 1;
+
+                fn checked_add(self, other: $unit) -> Option<$unit> { <$unit>::checked_add(self, other) }
+                fn checked_sub(self, other: $unit) -> Option<$unit> { <$unit>::checked_sub(self, other) }
+
+                fn to_usize(self) -> usize { self as usize }
+                fn from_usize(value: usize) -> $unit { value as $unit }
+            }
+        )*
+    };
+}
+
+impl_coordinate!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+// ======================================================== AUXILIARY FUNCTIONS ========================================================
+
+fn absolute_difference<T: Coordinate>(value_1: T, value_2: T) -> T {
+    return if value_1 > value_2 { value_1 - value_2 } else { value_2 - value_1 };
+}
+
+fn shift<T: Coordinate>(value: T, direction: i8) -> Option<T> {
+    return match direction.cmp(&0) {
+        Ordering::Less => value.checked_sub(T::ONE),
+        Ordering::Equal => Some(value),
+        Ordering::Greater => value.checked_add(T::ONE),
+    };
+}
+
+// ====================================================== STRUCTS IMPLEMENTATIONS ======================================================
+
+impl<T: Coordinate> Point<T> {
+    pub fn new(x: T, y: T) -> Point<T> {
+        Point { x: x, y: y }
+    }
+
+    pub fn get_x(&self) -> T { self.x }
+    pub fn get_y(&self) -> T { self.y }
+
+    pub fn manhattan_distance(&self, other: &Point<T>) -> T {
+        return absolute_difference(self.x, other.x) + absolute_difference(self.y, other.y);
+    }
+
+    pub fn chebyshev_distance(&self, other: &Point<T>) -> T {
+        return std::cmp::max(absolute_difference(self.x, other.x), absolute_difference(self.y, other.y));
+    }
+
+    fn shifted_by(&self, directions: &'static [(i8, i8)]) -> impl Iterator<Item = Point<T>> + '_ {
+        return directions.iter()
+            .filter_map(|&(direction_x, direction_y)| Some(Point::new(shift(self.x, direction_x)?, shift(self.y, direction_y)?)));
+    }
+
+    /// Up to four points sharing a side with this one, in reading order (those out of the unit range are skipped).
+    pub fn orthogonal_neighbours(&self) -> impl Iterator<Item = Point<T>> + '_ {
+        return self.shifted_by(&ORTHOGONAL_DIRECTIONS);
+    }
+
+    /// Up to eight points sharing a side or a corner with this one, in reading order (those out of the unit range are skipped).
+    pub fn neighbours(&self) -> impl Iterator<Item = Point<T>> + '_ {
+        return self.shifted_by(&ALL_DIRECTIONS);
+    }
+}
+
+impl<T: Coordinate> Ord for Point<T> {
+    fn cmp(&self, other: &Point<T>) -> Ordering {
+        return self.y.cmp(&other.y).then(self.x.cmp(&other.x));
+    }
+}
+
+impl<T: Coordinate> PartialOrd for Point<T> {
+    fn partial_cmp(&self, other: &Point<T>) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T: Coordinate> Add for Point<T> {
+    type Output = Point<T>;
+    fn add(self, other: Point<T>) -> Point<T> { Point::new(self.x + other.x, self.y + other.y) }
+}
+
+impl<T: Coordinate> Sub for Point<T> {
+    type Output = Point<T>;
+    fn sub(self, other: Point<T>) -> Point<T> { Point::new(self.x - other.x, self.y - other.y) }
+}
+
+impl<T: Coordinate> Mul<T> for Point<T> {
+    type Output = Point<T>;
+    fn mul(self, factor: T) -> Point<T> { Point::new(self.x * factor, self.y * factor) }
+}
+
+impl<T: Coordinate + Neg<Output = T>> Neg for Point<T> {
+    type Output = Point<T>;
+    fn neg(self) -> Point<T> { Point::new(-self.x, -self.y) }
+}
+
+impl<T: Coordinate> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Point<T>) { *self = *self + other; }
+}
+
+impl<T: Coordinate> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Point<T>) { *self = *self - other; }
+}
+
+impl<T: Coordinate> BoundingBox<T> {
+    pub fn new(min: Point<T>, max: Point<T>) -> BoundingBox<T> {
+        BoundingBox { min: min, max: max }
+    }
+
+    /// Smallest box containing every point, or `None` when there are no points.
+    pub fn from_points<'a, I: IntoIterator<Item = &'a Point<T>>>(points: I) -> Option<BoundingBox<T>> where T: 'a {
+        let mut points_iterator = points.into_iter();
+        let first_point : Point<T> = *points_iterator.next()?;
+        let mut bounding_box : BoundingBox<T> = BoundingBox::new(first_point, first_point);
+        points_iterator.for_each(|point| bounding_box.include(point));
+
+        return Some(bounding_box);
+    }
+
+    pub fn include(&mut self, point: &Point<T>) {
+        self.min = Point::new(std::cmp::min(self.min.x, point.x), std::cmp::min(self.min.y, point.y));
+        self.max = Point::new(std::cmp::max(self.max.x, point.x), std::cmp::max(self.max.y, point.y));
+    }
+
+    /// Same box grown by `margin` on every side.
+    pub fn expand(&self, margin: T) -> BoundingBox<T> {
+        return BoundingBox::new(self.min - Point::new(margin, margin), self.max + Point::new(margin, margin));
+    }
+
+    pub fn width(&self) -> T { self.max.x - self.min.x + T::ONE }
+    pub fn height(&self) -> T { self.max.y - self.min.y + T::ONE }
+    pub fn is_empty(&self) -> bool { self.min.x > self.max.x || self.min.y > self.max.y }
+
+    pub fn contains(&self, point: &Point<T>) -> bool {
+        return self.min.x <= point.x && point.x <= self.max.x && self.min.y <= point.y && point.y <= self.max.y;
+    }
+
+    pub fn is_on_border(&self, point: &Point<T>) -> bool {
+        return self.contains(point) && (point.x == self.min.x || point.x == self.max.x || point.y == self.min.y || point.y == self.max.y);
+    }
+
+    /// Every point of the box, in reading order.
+    pub fn points(&self) -> impl Iterator<Item = Point<T>> {
+        let bounding_box : BoundingBox<T> = *self;
+        let mut next_point : Option<Point<T>> = if self.is_empty() { None } else { Some(self.min) };
+
+        return std::iter::from_fn(move || {
+            let point : Point<T> = next_point?;
+            next_point = if point.x < bounding_box.max.x { Some(Point::new(point.x + T::ONE, point.y)) }
+                else if point.y < bounding_box.max.y { Some(Point::new(bounding_box.min.x, point.y + T::ONE)) }
+                else { None };
+            return Some(point);
+        });
+    }
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_distances() {
+        let (point_1, point_2) : (Point<i64>, Point<i64>) = (Point::new(1, 6), Point::new(-3, 4));
+        assert_eq!(point_1.manhattan_distance(&point_2), 6);
+        assert_eq!(point_1.chebyshev_distance(&point_2), 4);
+        assert_eq!(Point::new(3u64, 5).manhattan_distance(&Point::new(5, 1)), 6);
+    }
+
+    #[test]
+    fn sorts_points_in_reading_order() {
+        let mut points : Vec<Point<i64>> = vec![Point::new(2, 1), Point::new(5, 0), Point::new(0, 1), Point::new(9, 0)];
+        points.sort();
+        assert_eq!(points, vec![Point::new(5, 0), Point::new(9, 0), Point::new(0, 1), Point::new(2, 1)]);
+    }
+
+    #[test]
+    fn skips_neighbours_out_of_range() {
+        assert_eq!(Point::new(1i64, 1).orthogonal_neighbours().collect::<Vec<_>>(),
+            vec![Point::new(1, 0), Point::new(0, 1), Point::new(2, 1), Point::new(1, 2)]);
+        assert_eq!(Point::new(0u64, 0).neighbours().collect::<Vec<_>>(), vec![Point::new(1, 0), Point::new(0, 1), Point::new(1, 1)]);
+    }
+
+    #[test]
+    fn bounds_points() {
+        let points : Vec<Point<i64>> = vec![Point::new(1, 1), Point::new(3, -2), Point::new(-1, 0)];
+        let bounding_box : BoundingBox<i64> = BoundingBox::from_points(&points).unwrap();
+        assert_eq!(bounding_box, BoundingBox::new(Point::new(-1, -2), Point::new(3, 1)));
+        assert_eq!((bounding_box.width(), bounding_box.height()), (5, 4));
+        assert_eq!(bounding_box.points().count(), 20);
+        assert_eq!(bounding_box.points().filter(|point| bounding_box.is_on_border(point)).count(), 14);
+        assert_eq!(bounding_box.expand(1).points().next(), Some(Point::new(-2, -3)));
+        assert!(BoundingBox::<i64>::from_points(&[]).is_none());
+    }
+}
\ No newline at end of file
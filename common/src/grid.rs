@@ -0,0 +1,194 @@
+use std::collections::VecDeque;
+use std::ops::{Index, IndexMut};
+
+use crate::geometry::{BoundingBox, Coordinate, Point};
+use crate::image::{Color, Image};
+
+// ======================================================== STRUCTS DEFINITIONS ========================================================
+
+/// Dense rectangular grid storing one value per point of its bounds, which may start anywhere (even at negative coordinates).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Grid<T, U = i64> {
+    bounds: BoundingBox<U>,
+    width: usize,
+    cells: Vec<T>,
+}
+
+// ====================================================== STRUCTS IMPLEMENTATIONS ======================================================
+
+impl<T, U: Coordinate> Grid<T, U> {
+    /// Grid over `bounds`, with every value computed from its point (an empty grid when the bounds are empty).
+    pub fn from_fn<F: FnMut(Point<U>) -> T>(bounds: BoundingBox<U>, generator: F) -> Grid<T, U> {
+        let width : usize = if bounds.is_empty() { 0 } else { bounds.width().to_usize() };
+        return Grid { bounds: bounds, width: width, cells: bounds.points().map(generator).collect() };
+    }
+
+    pub fn new(bounds: BoundingBox<U>, value: T) -> Grid<T, U> where T: Clone {
+        return Grid::from_fn(bounds, |_| value.clone());
+    }
+
+    pub fn get_bounds(&self) -> BoundingBox<U> { self.bounds }
+    pub fn get_width(&self) -> usize { self.width }
+    pub fn get_height(&self) -> usize { self.cells.len().checked_div(self.width).unwrap_or(0) }
+
+    pub fn contains(&self, point: &Point<U>) -> bool {
+        return self.width > 0 && self.bounds.contains(point);
+    }
+
+    fn index_of(&self, point: &Point<U>) -> Option<usize> {
+        if !self.contains(point) { return None; }
+        let offset : Point<U> = *point - self.bounds.min;
+        return Some(offset.y.to_usize() * self.width + offset.x.to_usize());
+    }
+
+    fn point_of(&self, index: usize) -> Point<U> {
+        return self.bounds.min + Point::new(U::from_usize(index % self.width), U::from_usize(index / self.width));
+    }
+
+    pub fn get(&self, point: &Point<U>) -> Option<&T> {
+        return self.index_of(point).map(|index| &self.cells[index]);
+    }
+
+    pub fn get_mut(&mut self, point: &Point<U>) -> Option<&mut T> {
+        return self.index_of(point).map(|index| &mut self.cells[index]);
+    }
+
+    /// Replaces the value at `point`, returning `false` (and leaving the grid untouched) when it is out of bounds.
+    pub fn set(&mut self, point: &Point<U>, value: T) -> bool {
+        return match self.get_mut(point) {
+            Some(cell) => { *cell = value; true },
+            None => false,
+        };
+    }
+
+    /// Points and values of the grid, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point<U>, &T)> + '_ {
+        return self.cells.iter().enumerate().map(|(index, value)| (self.point_of(index), value));
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> + '_ {
+        return self.cells.iter();
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> + '_ {
+        return self.cells.iter_mut();
+    }
+
+    /// Points of the grid sharing a side with `point`, along with their values.
+    pub fn orthogonal_neighbours(&self, point: Point<U>) -> impl Iterator<Item = (Point<U>, &T)> + '_ {
+        return point.orthogonal_neighbours()
+            .collect::<Vec<Point<U>>>().into_iter()
+            .filter_map(move |neighbour| Some((neighbour, self.get(&neighbour)?)));
+    }
+
+    /// Points of the grid sharing a side or a corner with `point`, along with their values.
+    pub fn neighbours(&self, point: Point<U>) -> impl Iterator<Item = (Point<U>, &T)> + '_ {
+        return point.neighbours()
+            .collect::<Vec<Point<U>>>().into_iter()
+            .filter_map(move |neighbour| Some((neighbour, self.get(&neighbour)?)));
+    }
+
+    /// Region of points orthogonally connected to `start` whose values satisfy `predicate`, in the order they are reached.
+    pub fn flood_fill<F: Fn(Point<U>, &T) -> bool>(&self, start: Point<U>, predicate: F) -> Vec<Point<U>> {
+        let mut region : Vec<Point<U>> = Vec::new();
+        let start_index : Option<usize> = self.index_of(&start).filter(|&index| predicate(start, &self.cells[index]));
+        if start_index.is_none() { return region; }
+
+        let mut visited : Vec<bool> = vec![false; self.cells.len()];
+        let mut pending : VecDeque<Point<U>> = VecDeque::from([start]);
+        visited[start_index.unwrap()] = true;
+        while let Some(point) = pending.pop_front() {
+            region.push(point);
+            for (neighbour, value) in self.orthogonal_neighbours(point) {
+                let neighbour_index : usize = self.index_of(&neighbour).unwrap();
+                if visited[neighbour_index] || !predicate(neighbour, value) { continue; }
+
+                visited[neighbour_index] = true;
+                pending.push_back(neighbour);
+            }
+        }
+
+        return region;
+    }
+
+    /// One line of text per row, with a character for every value.
+    pub fn render_text<F: Fn(Point<U>, &T) -> char>(&self, render: F) -> String {
+        let mut final_string : String = String::with_capacity(self.cells.len() + self.get_height());
+        for (index, value) in self.cells.iter().enumerate() {
+            final_string.push(render(self.point_of(index), value));
+            if (index + 1) % self.width == 0 { final_string.push('\n'); }
+        }
+
+        return final_string;
+    }
+
+    /// Binary PPM (`P6`) image with one pixel per value, colored as an RGB triplet.
+    pub fn render_ppm<F: Fn(Point<U>, &T) -> Color>(&self, render: F) -> Vec<u8> {
+        return Image::from_grid(self, render).to_ppm();
+    }
+}
+
+impl<T, U: Coordinate> Index<Point<U>> for Grid<T, U> {
+    type Output = T;
+
+    fn index(&self, point: Point<U>) -> &T {
+        return self.get(&point).unwrap_or_else(|| panic!("point {} is out of the grid bounds", point));
+    }
+}
+
+impl<T, U: Coordinate> IndexMut<Point<U>> for Grid<T, U> {
+    fn index_mut(&mut self, point: Point<U>) -> &mut T {
+        return self.get_mut(&point).unwrap_or_else(|| panic!("point {} is out of the grid bounds", point));
+    }
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offset_grid() -> Grid<char> {
+        let rows : [&str; 3] = ["#..", "#.#", "..#"];
+        return Grid::from_fn(BoundingBox::new(Point::new(-1, -1), Point::new(1, 1)),
+            |point| rows[(point.y + 1) as usize].chars().nth((point.x + 1) as usize).unwrap());
+    }
+
+    #[test]
+    fn indexes_points_from_offset_origin() {
+        let mut grid : Grid<char> = offset_grid();
+        assert_eq!((grid.get_width(), grid.get_height()), (3, 3));
+        assert_eq!(grid[Point::new(-1, -1)], '#');
+        assert_eq!(grid.get(&Point::new(1, 0)), Some(&'#'));
+        assert_eq!(grid.get(&Point::new(2, 0)), None);
+
+        assert!(grid.set(&Point::new(0, 0), '#'));
+        assert!(!grid.set(&Point::new(0, 2), '#'));
+        assert_eq!(grid.iter().filter(|(_, &value)| value == '#').count(), 5);
+    }
+
+    #[test]
+    fn queries_neighbourhoods_inside_bounds() {
+        let grid : Grid<char> = offset_grid();
+        assert_eq!(grid.orthogonal_neighbours(Point::new(-1, -1)).count(), 2);
+        assert_eq!(grid.neighbours(Point::new(0, 0)).filter(|(_, &value)| value == '#').count(), 4);
+    }
+
+    #[test]
+    fn fills_connected_regions() {
+        let grid : Grid<char> = offset_grid();
+        let region : Vec<Point<i64>> = grid.flood_fill(Point::new(0, -1), |_, &value| value == '.');
+        assert_eq!(region.len(), 5);
+        assert!(grid.flood_fill(Point::new(-1, -1), |_, &value| value == '.').is_empty());
+    }
+
+    #[test]
+    fn renders_text_and_ppm() {
+        let grid : Grid<char> = offset_grid();
+        assert_eq!(grid.render_text(|_, &value| value), "#..\n#.#\n..#\n");
+
+        let image : Vec<u8> = grid.render_ppm(|_, &value| if value == '#' { [255, 255, 255] } else { [0, 0, 0] });
+        assert!(image.starts_with(b"P6\n3 3\n255\n"));
+        assert_eq!(image.len(), 11 + 3 * 9);
+    }
+}
\ No newline at end of file
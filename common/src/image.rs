@@ -0,0 +1,231 @@
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::geometry::{Coordinate, Point};
+use crate::grid::Grid;
+
+// ======================================================== STRUCTS DEFINITIONS ========================================================
+
+pub type Color = [u8; 3];
+
+/// RGB raster image, written as PPM, PNG or SVG depending on the extension of the output path.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ImageFormat {
+    Ppm,
+    Png,
+    Svg,
+}
+
+// ======================================================= CONSTANTS DEFINITIONS =======================================================
+
+pub const BLACK : Color = [0, 0, 0];
+pub const WHITE : Color = [255, 255, 255];
+
+const GOLDEN_RATIO_CONJUGATE : f64 = 0.618_033_988_749_895;
+
+// ======================================================== AUXILIARY FUNCTIONS ========================================================
+
+fn hsv_color(hue: f64, saturation: f64, value: f64) -> Color {
+    let sector : f64 = (hue.rem_euclid(1.0)) * 6.0;
+    let fraction : f64 = sector - sector.floor();
+    let (p, q, t) : (f64, f64, f64) = (value * (1.0 - saturation), value * (1.0 - saturation * fraction), value * (1.0 - saturation * (1.0 - fraction)));
+    let (red, green, blue) : (f64, f64, f64) = match sector as usize {
+        0 => (value, t, p),
+        1 => (q, value, p),
+        2 => (p, value, t),
+        3 => (p, q, value),
+        4 => (t, p, value),
+        _ => (value, p, q),
+    };
+
+    return [(red * 255.0).round() as u8, (green * 255.0).round() as u8, (blue * 255.0).round() as u8];
+}
+
+/// Color of a heatmap for a value between 0 and 1 (clamped), going from black through red and yellow to white.
+pub fn heat_color(value: f64) -> Color {
+    let value : f64 = value.clamp(0.0, 1.0) * 3.0;
+    let channel = |offset: f64| ((value - offset).clamp(0.0, 1.0) * 255.0).round() as u8;
+    return [channel(0.0), channel(1.0), channel(2.0)];
+}
+
+/// Distinct color for the given index, spreading the hues with the golden ratio so that close indexes do not look alike.
+pub fn palette_color(index: usize) -> Color {
+    return hsv_color(index as f64 * GOLDEN_RATIO_CONJUGATE, 0.65, 0.95);
+}
+
+/// Same color with its brightness multiplied by `factor`.
+pub fn shade_color(color: Color, factor: f64) -> Color {
+    return color.map(|channel| (channel as f64 * factor).round().clamp(0.0, 255.0) as u8);
+}
+
+/// Writes every frame: a single frame goes to `path`, several ones to `path` suffixed with the frame number.
+pub fn save_frames(frames: &[Image], path: &Path) -> io::Result<Vec<PathBuf>> {
+    if frames.len() == 1 {
+        frames[0].save(path)?;
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let stem : String = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+    let extension : String = path.extension().map(|extension| extension.to_string_lossy().into_owned()).unwrap_or_default();
+    let mut frame_paths : Vec<PathBuf> = Vec::new();
+    for (index, frame) in frames.iter().enumerate() {
+        let frame_path : PathBuf = path.with_file_name(format!("{}_{:05}.{}", stem, index, extension));
+        frame.save(&frame_path)?;
+        frame_paths.push(frame_path);
+    }
+
+    return Ok(frame_paths);
+}
+
+// ====================================================== STRUCTS IMPLEMENTATIONS ======================================================
+
+impl ImageFormat {
+    pub fn from_path(path: &Path) -> Option<ImageFormat> {
+        let extension : String = path.extension()?.to_string_lossy().to_lowercase();
+        return match extension.as_str() {
+            "ppm" => Some(ImageFormat::Ppm),
+            "png" => Some(ImageFormat::Png),
+            "svg" => Some(ImageFormat::Svg),
+            _ => None,
+        };
+    }
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, color: Color) -> Image {
+        Image { width: width, height: height, pixels: vec![color; width * height] }
+    }
+
+    /// One pixel per point of the grid, in the same layout.
+    pub fn from_grid<T, U: Coordinate, F: Fn(Point<U>, &T) -> Color>(grid: &Grid<T, U>, color: F) -> Image {
+        return Image {
+            width: grid.get_width(),
+            height: grid.get_height(),
+            pixels: grid.iter().map(|(point, value)| color(point, value)).collect(),
+        };
+    }
+
+    pub fn get_width(&self) -> usize { self.width }
+    pub fn get_height(&self) -> usize { self.height }
+
+    pub fn get_pixel(&self, x: usize, y: usize) -> Option<Color> {
+        if x >= self.width || y >= self.height { return None; }
+        return Some(self.pixels[y * self.width + x]);
+    }
+
+    pub fn set_pixel(&mut self, x: usize, y: usize, color: Color) {
+        if x < self.width && y < self.height { self.pixels[y * self.width + x] = color; }
+    }
+
+    /// Image enlarged by an integer factor, every pixel becoming a square of `factor` x `factor` pixels.
+    pub fn scale(&self, factor: usize) -> Image {
+        let mut scaled : Image = Image::new(self.width * factor, self.height * factor, BLACK);
+        for y in 0..scaled.height {
+            for x in 0..scaled.width {
+                scaled.pixels[y * scaled.width + x] = self.pixels[(y / factor) * self.width + x / factor];
+            }
+        }
+
+        return scaled;
+    }
+
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut image : Vec<u8> = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        self.pixels.iter().for_each(|pixel| image.extend_from_slice(pixel));
+        return image;
+    }
+
+    pub fn to_png(&self) -> io::Result<Vec<u8>> {
+        let mut image : Vec<u8> = Vec::new();
+        let mut encoder = png::Encoder::new(&mut image, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer.write_image_data(&self.pixels.concat()).map_err(io::Error::other)?;
+        writer.finish().map_err(io::Error::other)?;
+        return Ok(image);
+    }
+
+    /// SVG with one rectangle per horizontal run of pixels of the same color.
+    pub fn to_svg(&self) -> String {
+        let mut svg : String = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" shape-rendering=\"crispEdges\">\n",
+            self.width, self.height);
+        for y in 0..self.height {
+            let row : &[Color] = &self.pixels[y * self.width..(y + 1) * self.width];
+            let mut run_start : usize = 0;
+            for x in 1..=self.width {
+                if x < self.width && row[x] == row[run_start] { continue; }
+
+                let [red, green, blue] = row[run_start];
+                writeln!(svg, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"1\" fill=\"#{:02x}{:02x}{:02x}\"/>", run_start, y, x - run_start, red, green, blue).unwrap();
+                run_start = x;
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        return svg;
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let image_format : ImageFormat = ImageFormat::from_path(path).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput,
+            format!("unsupported image format for '{}' (expected '.ppm', '.png' or '.svg')", path.display())))?;
+
+        return match image_format {
+            ImageFormat::Ppm => fs::write(path, self.to_ppm()),
+            ImageFormat::Png => fs::write(path, self.to_png()?),
+            ImageFormat::Svg => fs::write(path, self.to_svg()),
+        };
+    }
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard() -> Image {
+        let mut image : Image = Image::new(2, 2, BLACK);
+        image.set_pixel(0, 0, WHITE);
+        image.set_pixel(1, 1, WHITE);
+        return image;
+    }
+
+    #[test]
+    fn scales_pixels_into_squares() {
+        let image : Image = checkerboard().scale(2);
+        assert_eq!((image.get_width(), image.get_height()), (4, 4));
+        assert_eq!(image.get_pixel(1, 1), Some(WHITE));
+        assert_eq!(image.get_pixel(2, 1), Some(BLACK));
+        assert_eq!(image.get_pixel(4, 0), None);
+    }
+
+    #[test]
+    fn encodes_every_format() {
+        let image : Image = checkerboard();
+        assert_eq!(image.to_ppm(), [b"P6\n2 2\n255\n".as_slice(), &[255, 255, 255, 0, 0, 0, 0, 0, 0, 255, 255, 255]].concat());
+        assert!(image.to_png().unwrap().starts_with(b"\x89PNG"));
+        assert_eq!(image.to_svg().matches("<rect").count(), 4);
+        assert_eq!(Image::new(3, 1, WHITE).to_svg().matches("<rect").count(), 1);
+        assert_eq!(ImageFormat::from_path(Path::new("out.PNG")), Some(ImageFormat::Png));
+        assert_eq!(ImageFormat::from_path(Path::new("out.gif")), None);
+    }
+
+    #[test]
+    fn builds_color_scales() {
+        assert_eq!((heat_color(0.0), heat_color(1.0), heat_color(2.0)), (BLACK, WHITE, WHITE));
+        assert_ne!(palette_color(0), palette_color(1));
+        assert_eq!(shade_color([200, 100, 0], 0.5), [100, 50, 0]);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,19 @@
+mod config;
+mod example;
+mod geometry;
+mod grid;
+mod image;
+mod logging;
+mod output;
+mod solution;
+mod stats;
+
+pub use config::{SolutionConfig, solution_from_args};
+pub use example::{Example, ExampleOutcome, check_example, example_from_args, print_example_outcomes, run_example};
+pub use geometry::{BoundingBox, Coordinate, Point};
+pub use grid::Grid;
+pub use image::{BLACK, WHITE, Color, Image, ImageFormat, heat_color, palette_color, save_frames, shade_color};
+pub use logging::{init_logging, verbosity_from_args};
+pub use output::{OutputFormat, format_answer, print_json_answers, render_path_from_args, save_render};
+pub use solution::{DayNumber, PartNumber, PartAnswer, ProfiledAnswers, Solution, SolutionError, SolutionResult, fuzz_parse, profile_solution, render_solution, run_solution};
+pub use stats::{CountingAllocator, PhaseStats, format_bytes, measure_phase, print_solution_stats, print_stats, stats_from_args};
\ No newline at end of file
@@ -0,0 +1,55 @@
+use std::env;
+use std::io::{self, IsTerminal};
+
+use tracing::Level;
+
+// ======================================================== AUXILIARY FUNCTIONS ========================================================
+
+fn count_verbosity<I: IntoIterator<Item = String>>(arguments: I) -> u8 {
+    let verbosity : usize = arguments.into_iter()
+        .map(|argument| match argument.as_str() {
+            "--verbose" => 1,
+            flags if flags.len() > 1 && flags.starts_with('-') && flags[1..].chars().all(|flag| flag == 'v') => flags.len() - 1,
+            _ => 0,
+        })
+        .sum();
+
+    return verbosity.min(u8::MAX as usize) as u8;
+}
+
+/// Number of `-v` flags of the process arguments (`-vv` counting twice, as does a repeated `--verbose`).
+pub fn verbosity_from_args() -> u8 {
+    return count_verbosity(env::args().skip(1));
+}
+
+/// Installs the global subscriber writing to stderr: warnings only by default, per-step summaries with `-v` and full state dumps with `-vv`.
+pub fn init_logging(verbosity: u8) {
+    let level : Level = match verbosity {
+        0 => Level::WARN,
+        1 => Level::DEBUG,
+        _ => Level::TRACE,
+    };
+
+    // A subscriber may already be installed (e.g. by a test), in which case it is kept
+    let _ = tracing_subscriber::fmt().with_max_level(level).with_writer(io::stderr).with_ansi(io::stderr().is_terminal())
+        .without_time().try_init();
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arguments(arguments: &[&str]) -> Vec<String> {
+        return arguments.iter().map(|argument| argument.to_string()).collect();
+    }
+
+    #[test]
+    fn counts_verbosity_flags() {
+        assert_eq!(count_verbosity(arguments(&["--format", "json"])), 0);
+        assert_eq!(count_verbosity(arguments(&["-v"])), 1);
+        assert_eq!(count_verbosity(arguments(&["-vv", "--format", "json"])), 2);
+        assert_eq!(count_verbosity(arguments(&["-v", "--verbose", "-"])), 2);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,110 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde_json::{Map, Value};
+
+use read::Input;
+
+use crate::image::{Image, save_frames};
+use crate::solution::{DayNumber, PartAnswer, Solution, SolutionResult, render_solution, run_solution};
+
+// ======================================================== STRUCTS DEFINITIONS ========================================================
+
+/// How answers are printed: decorated prose for humans, or structured JSON for scripts.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+// ====================================================== STRUCTS IMPLEMENTATIONS ======================================================
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<OutputFormat, String> {
+        return match format {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(format!("unknown output format '{}' (expected 'text' or 'json')", format)),
+        };
+    }
+}
+
+impl OutputFormat {
+    /// Reads the `--format <text|json>` (or `--format=<text|json>`) flag of the process arguments, defaulting to text.
+    pub fn from_args() -> Result<OutputFormat, String> {
+        return match flag_value("format")? {
+            Some(format) => format.parse(),
+            None => Ok(OutputFormat::Text),
+        };
+    }
+}
+
+impl PartAnswer {
+    /// JSON object with the day, part, answer and elapsed time, followed by the extra fields of the answer.
+    pub fn to_json(&self, day: DayNumber) -> Value {
+        let mut fields : Map<String, Value> = Map::new();
+        fields.insert("day".to_owned(), Value::from(day));
+        fields.insert("part".to_owned(), Value::from(self.part));
+        fields.insert("answer".to_owned(), Value::from(self.answer.clone()));
+        fields.insert("elapsed_ms".to_owned(), Value::from(self.elapsed.as_secs_f64() * 1000.0));
+        if !self.extra.is_empty() {
+            fields.insert("extra".to_owned(), Value::Object(self.extra.clone()));
+        }
+
+        return Value::Object(fields);
+    }
+}
+
+// ======================================================== AUXILIARY FUNCTIONS ========================================================
+
+/// Values of every `--<name> <value>` (or `--<name>=<value>`) flag of the process arguments, in order.
+pub(crate) fn flag_values(name: &str) -> Result<Vec<String>, String> {
+    let (flag, prefix) : (String, String) = (format!("--{}", name), format!("--{}=", name));
+    let arguments : Vec<String> = env::args().skip(1).collect();
+    let mut values : Vec<String> = Vec::new();
+    let mut index : usize = 0;
+    while index < arguments.len() {
+        if let Some(value) = arguments[index].strip_prefix(&prefix) { values.push(value.to_owned()); }
+        else if arguments[index] == flag {
+            values.push(arguments.get(index + 1).cloned().ok_or(format!("missing value for '{}'", flag))?);
+            index = index + 1;
+        }
+        index = index + 1;
+    }
+
+    return Ok(values);
+}
+
+/// Value of the first `--<name> <value>` (or `--<name>=<value>`) flag of the process arguments, if present.
+pub(crate) fn flag_value(name: &str) -> Result<Option<String>, String> {
+    return Ok(flag_values(name)?.into_iter().next());
+}
+
+/// Answer between quotes, or on its own lines when it spans several ones.
+pub fn format_answer(answer: &str) -> String {
+    return if answer.contains('\n') { format!("\n{}", answer) } else { format!("'{}'", answer) };
+}
+
+/// Reads the `--render <path>` flag of the process arguments, the extension of the path choosing the image format.
+pub fn render_path_from_args() -> Result<Option<PathBuf>, String> {
+    return Ok(flag_value("render")?.map(PathBuf::from));
+}
+
+/// Solves both parts and prints them as a JSON array, as the `--format json` mode of the day binaries.
+pub fn print_json_answers<S: Solution>(solution: &S, input: &Input) -> SolutionResult<()> {
+    let answers : Vec<Value> = run_solution(solution, input, &[1, 2])?.iter()
+        .map(|answer| answer.to_json(S::DAY))
+        .collect();
+
+    println!("{}", Value::Array(answers));
+    return Ok(());
+}
+
+/// Renders the visualization of the input and writes its frames, returning the paths of the written images.
+pub fn save_render<S: Solution>(solution: &S, input: &Input, path: &Path) -> SolutionResult<Vec<PathBuf>> {
+    let frames : Vec<Image> = render_solution(solution, input)?;
+    return Ok(save_frames(&frames, path)?);
+}
\ No newline at end of file
@@ -0,0 +1,113 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use read::Input;
+
+use crate::example::Example;
+use crate::image::Image;
+use crate::stats::{PhaseStats, measure_phase};
+
+// ======================================================== STRUCTS DEFINITIONS ========================================================
+
+pub type DayNumber = u8;
+pub type PartNumber = u8;
+
+pub type SolutionError = Box<dyn Error + Send + Sync>;
+pub type SolutionResult<T> = Result<T, SolutionError>;
+
+/// Answers of the solved parts, with the stats of the parsing and of every part.
+pub type ProfiledAnswers = (Vec<PartAnswer>, Vec<PhaseStats>);
+
+/// Answer of a part as text, with the fields of structured answers.
+type RawAnswer = (String, Map<String, Value>);
+
+pub struct PartAnswer {
+    pub part: PartNumber,
+    pub answer: String,
+    pub elapsed: Duration,
+    /// Fields of structured answers (e.g. the guard and minute behind a guard code), empty for plain values
+    pub extra: Map<String, Value>,
+}
+
+// ======================================================== TRAITS DEFINITIONS =========================================================
+
+/// Puzzle of a single day, split into the parsing of the raw input and the two parts solved over it.
+pub trait Solution {
+    const DAY: DayNumber;
+    const TITLE: &'static str;
+
+    type Input;
+    type Part1: Display + Serialize;
+    type Part2: Display + Serialize;
+    type Error: Error + Send + Sync + 'static;
+
+    fn parse(&self, input: &Input) -> Result<Self::Input, Self::Error>;
+    fn part1(&self, input: &Self::Input) -> Result<Self::Part1, Self::Error>;
+    fn part2(&self, input: &Self::Input) -> Result<Self::Part2, Self::Error>;
+
+    /// Frames visualizing the parsed input (a single one for static pictures), none for the days without a visualization.
+    fn render(&self, _input: &Self::Input) -> Result<Vec<Image>, Self::Error> {
+        return Ok(Vec::new());
+    }
+
+    /// Examples of the puzzle statement with their expected answers, run by `--example`.
+    fn examples() -> Vec<Example<Self>> where Self: Sized {
+        return Vec::new();
+    }
+}
+
+// ======================================================== AUXILIARY FUNCTIONS ========================================================
+
+fn answer_extra<T: Serialize>(answer: &T) -> SolutionResult<Map<String, Value>> {
+    return match serde_json::to_value(answer)? {
+        Value::Object(fields) => Ok(fields),
+        _ => Ok(Map::new()),
+    };
+}
+
+/// Parses the input once and solves the requested parts over it, timing each part.
+pub fn run_solution<S: Solution>(solution: &S, input: &Input, parts: &[PartNumber]) -> SolutionResult<Vec<PartAnswer>> {
+    return Ok(profile_solution(solution, input, parts)?.0);
+}
+
+/// Solves the requested parts as `run_solution`, along with the time and heap peak of the parsing and of every part.
+pub fn profile_solution<S: Solution>(solution: &S, input: &Input, parts: &[PartNumber]) -> SolutionResult<ProfiledAnswers> {
+    let (parsed_input, parse_stats) : (Result<S::Input, S::Error>, PhaseStats) = measure_phase("parse", || solution.parse(input));
+    let parsed_input : S::Input = parsed_input?;
+
+    let mut answers : Vec<PartAnswer> = Vec::new();
+    let mut phases_stats : Vec<PhaseStats> = vec![parse_stats];
+    for &part in parts.iter() {
+        let (answer, part_stats) : (SolutionResult<RawAnswer>, PhaseStats) = measure_phase(&format!("part {}", part), || {
+            return match part {
+                1 => { let answer : S::Part1 = solution.part1(&parsed_input)?; Ok((answer.to_string(), answer_extra(&answer)?)) },
+                2 => { let answer : S::Part2 = solution.part2(&parsed_input)?; Ok((answer.to_string(), answer_extra(&answer)?)) },
+                _ => Err(format!("Part '{}' does not exist", part).into()),
+            };
+        });
+        let (answer, extra) : RawAnswer = answer?;
+
+        answers.push(PartAnswer { part: part, answer: answer, elapsed: part_stats.elapsed, extra: extra });
+        phases_stats.push(part_stats);
+    }
+
+    return Ok((answers, phases_stats));
+}
+
+/// Parses the input and renders its visualization, failing for the days without one.
+pub fn render_solution<S: Solution>(solution: &S, input: &Input) -> SolutionResult<Vec<Image>> {
+    let frames : Vec<Image> = solution.render(&solution.parse(input)?)?;
+    if frames.is_empty() { return Err(format!("'{}' has no visualization", S::TITLE).into()); }
+
+    return Ok(frames);
+}
+
+/// Entry point of the fuzz targets: parses arbitrary bytes with the default solution, which must fail gracefully instead of panicking.
+pub fn fuzz_parse<S: Solution + Default>(data: &[u8]) {
+    let Ok(text) = std::str::from_utf8(data) else { return; };
+    let _ = S::default().parse(&Input::from_string("fuzz", text));
+}
\ No newline at end of file
@@ -0,0 +1,149 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::env;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use read::Input;
+
+use crate::output::format_answer;
+use crate::solution::{DayNumber, ProfiledAnswers, Solution, SolutionResult, profile_solution};
+
+// ======================================================== STRUCTS DEFINITIONS ========================================================
+
+/// Allocator of the system, counting the bytes in use and their peak. Binaries install it as their `#[global_allocator]`, without
+/// it every heap peak reads as zero.
+pub struct CountingAllocator;
+
+/// Wall time and heap peak of a phase of a solution (the parsing or one of the parts).
+#[derive(Clone, Debug)]
+pub struct PhaseStats {
+    pub phase: String,
+    pub elapsed: Duration,
+    /// Largest number of bytes in use over the phase, on top of the ones already in use when it began
+    pub peak_heap: usize,
+}
+
+// ======================================================= CONSTANTS DEFINITIONS =======================================================
+
+static CURRENT_BYTES : AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES : AtomicUsize = AtomicUsize::new(0);
+
+const BYTE_UNITS : [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+// ====================================================== STRUCTS IMPLEMENTATIONS ======================================================
+
+impl CountingAllocator {
+    fn allocated(size: usize) {
+        let current_bytes : usize = CURRENT_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_BYTES.fetch_max(current_bytes, Ordering::Relaxed);
+    }
+
+    fn deallocated(size: usize) {
+        CURRENT_BYTES.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let pointer : *mut u8 = unsafe { System.alloc(layout) };
+        if !pointer.is_null() { CountingAllocator::allocated(layout.size()); }
+        return pointer;
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let pointer : *mut u8 = unsafe { System.alloc_zeroed(layout) };
+        if !pointer.is_null() { CountingAllocator::allocated(layout.size()); }
+        return pointer;
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(pointer, layout) };
+        CountingAllocator::deallocated(layout.size());
+    }
+
+    unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_pointer : *mut u8 = unsafe { System.realloc(pointer, layout, new_size) };
+        if !new_pointer.is_null() {
+            CountingAllocator::deallocated(layout.size());
+            CountingAllocator::allocated(new_size);
+        }
+        return new_pointer;
+    }
+}
+
+// ======================================================== AUXILIARY FUNCTIONS ========================================================
+
+/// Runs `phase`, timing it and following the heap peak it reaches. Phases are measured one at a time: the counters are shared by
+/// the whole process.
+pub fn measure_phase<T, F: FnOnce() -> T>(name: &str, phase: F) -> (T, PhaseStats) {
+    let baseline_bytes : usize = CURRENT_BYTES.load(Ordering::Relaxed);
+    PEAK_BYTES.store(baseline_bytes, Ordering::Relaxed);
+
+    let start : Instant = Instant::now();
+    let result : T = phase();
+    let elapsed : Duration = start.elapsed();
+
+    let peak_heap : usize = PEAK_BYTES.load(Ordering::Relaxed).saturating_sub(baseline_bytes);
+    return (result, PhaseStats { phase: name.to_owned(), elapsed: elapsed, peak_heap: peak_heap });
+}
+
+/// Reads the `--stats` flag of the process arguments.
+pub fn stats_from_args() -> bool {
+    return env::args().skip(1).any(|argument| argument == "--stats");
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    let mut value : f64 = bytes as f64;
+    let mut unit : usize = 0;
+    while value >= 1024.0 && unit + 1 < BYTE_UNITS.len() {
+        value = value / 1024.0;
+        unit = unit + 1;
+    }
+
+    return if unit == 0 { format!("{} B", bytes) } else { format!("{:.1} {}", value, BYTE_UNITS[unit]) };
+}
+
+/// Prints the time and heap peak of every phase of the given days as a table.
+pub fn print_stats(days_stats: &[(DayNumber, Vec<PhaseStats>)]) {
+    println!(" Day | Phase  |         Time |    Peak heap");
+    println!("-----+--------+--------------+--------------");
+    for (day, phases_stats) in days_stats.iter() {
+        for phase_stats in phases_stats.iter() {
+            println!("  {:02} | {:<6} | {:>9.3} ms | {:>12}", day, phase_stats.phase, phase_stats.elapsed.as_secs_f64() * 1000.0,
+                format_bytes(phase_stats.peak_heap));
+        }
+    }
+}
+
+/// Solves both parts and prints their answers followed by the stats of every phase, as the `--stats` mode of the day binaries.
+pub fn print_solution_stats<S: Solution>(solution: &S, input: &Input) -> SolutionResult<()> {
+    let (answers, phases_stats) : ProfiledAnswers = profile_solution(solution, input, &[1, 2])?;
+    for answer in answers.iter() {
+        println!("🎄 Day {:02} (Part {}): {}", S::DAY, answer.part, format_answer(&answer.answer));
+    }
+
+    println!();
+    print_stats(&[(S::DAY, phases_stats)]);
+    return Ok(());
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_byte_sizes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn times_phases() {
+        let (value, phase_stats) : (usize, PhaseStats) = measure_phase("parse", || 7);
+        assert_eq!(value, 7);
+        assert_eq!(phase_stats.phase, "parse");
+    }
+}
\ No newline at end of file
@@ -1,29 +1,406 @@
-use std::collections::HashSet;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
 
-pub fn sum_values(starting_value: Option<i64>, values_list: &Vec<i64>) -> i64 {
+use serde::Serialize;
+
+use common::{DayNumber, Example, Solution};
+use read::{Input, ReadError, ReadErrorKind};
+
+// ======================================================== STRUCTS DEFINITIONS ========================================================
+
+/// Moment of the calibration at which a frequency is reached: the pass over the list (from one) and the index of the change that
+/// produced it, the starting frequency being reached on the first pass before any change.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+pub struct FrequencyVisit {
+    pub pass: u64,
+    pub change_index: Option<usize>,
+}
+
+/// Frequency reached again, with the moment it was reached again and the moment it was first seen.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+pub struct FrequencyRepeat {
+    pub frequency: i64,
+    pub reached: FrequencyVisit,
+    pub first_seen: FrequencyVisit,
+}
+
+#[derive(Debug)]
+pub enum Day01Error {
+    Read(ReadError),
+    NoFrequencyChanges,
+    NoRepeatedFrequency,
+    FrequencyOverflow,
+}
+
+// ====================================================== STRUCTS IMPLEMENTATIONS ======================================================
+
+impl fmt::Display for FrequencyRepeat {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}", self.frequency)
+    }
+}
+
+impl fmt::Display for Day01Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day01Error::Read(error) => write!(formatter, "{}", error),
+            Day01Error::NoFrequencyChanges => write!(formatter, "no frequency changes to be applied"),
+            Day01Error::NoRepeatedFrequency => write!(formatter, "no frequency is ever reached twice"),
+            Day01Error::FrequencyOverflow => write!(formatter, "frequency out of the range of a 64-bit integer"),
+        }
+    }
+}
+
+impl Error for Day01Error {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Day01Error::Read(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ReadError> for Day01Error {
+    fn from(error: ReadError) -> Day01Error { Day01Error::Read(error) }
+}
+
+/// Frequency changes separated by new lines or commas (`+1, -2, +3, +1` as in the puzzle examples), where a `#` starts a comment
+/// running to the end of the line and blank lines are skipped, as are trailing commas.
+pub fn parse_frequency_changes(input: &Input) -> Result<Vec<i64>, ReadError> {
+    let mut values_list : Vec<i64> = Vec::new();
+    for (line_index, line) in input.get_text().lines().enumerate() {
+        let content : &str = line.split('#').next().unwrap_or_default();
+        let tokens : Vec<&str> = content.split(',').collect();
+
+        let mut column_index : usize = 0;
+        for (token_index, token) in tokens.iter().enumerate() {
+            let trimmed_token : &str = token.trim();
+            let token_column : usize = column_index + token.chars().take_while(|character| character.is_whitespace()).count();
+            column_index = column_index + token.chars().count() + 1;
+
+            if trimmed_token.is_empty() && (token_index + 1 == tokens.len() || content.trim().is_empty()) { continue; }
+            let value : i64 = trimmed_token.parse::<i64>().map_err(|error| input.error_at(line_index, token_column,
+                ReadErrorKind::InvalidValue { value: trimmed_token.to_owned(), reason: error.to_string() }))?;
+            values_list.push(value);
+        }
+    }
+
+    return Ok(values_list);
+}
+
+/// Frequency after one pass over the changes, `None` when it leaves the range of `i64` along the way.
+pub fn sum_values(starting_value: Option<i64>, values_list: &Vec<i64>) -> Option<i64> {
     let mut current_value : i64 = starting_value.unwrap_or(0);
-    for value in values_list.iter() { current_value = current_value + value }
+    for value in values_list.iter() { current_value = current_value.checked_add(*value)?; }
+
+    return Some(current_value);
+}
+
+/// Moment at which the given number of changes have been applied.
+fn visit_after(applied_changes: u128, changes_count: usize) -> FrequencyVisit {
+    if applied_changes == 0 { return FrequencyVisit { pass: 1, change_index: None }; }
 
-    return current_value;
+    let passes : u128 = (applied_changes - 1) / changes_count as u128;
+    let change_index : usize = ((applied_changes - 1) % changes_count as u128) as usize;
+    return FrequencyVisit { pass: u64::try_from(passes + 1).unwrap_or(u64::MAX), change_index: Some(change_index) };
 }
 
-pub fn first_repeated_current(starting_value: Option<i64>, values_list: &Vec<i64>) -> i64 {
+/// First frequency reached twice when applying the changes over and over, without replaying them (see `trace_repeat`).
+pub fn trace_first_repeat(starting_value: Option<i64>, values_list: &Vec<i64>) -> Option<FrequencyRepeat> {
+    return trace_repeat(starting_value, values_list, 1);
+}
+
+pub fn first_repeated_current(starting_value: Option<i64>, values_list: &Vec<i64>) -> Option<i64> {
+    return trace_first_repeat(starting_value, values_list).map(|repeat| repeat.frequency);
+}
+
+/// Repeat of the given rank (from one), without replaying the changes: the frequency reached by the change `j` on pass `p` (from
+/// zero) is `s_j + p * drift`, so it can only meet the first-pass frequencies congruent to `s_j` modulo the drift. It meets one again
+/// from the pass at which it reaches the next of them in the direction of the drift, or from the start when an earlier change already
+/// reached `s_j`, and on every pass after that; the repeats are counted pass by pass from those starting passes. `None` when no
+/// frequency ever repeats, or when the first pass or the repeat leaves the range of `i64` (the passes in between are only reasoned
+/// about, in 128 bits).
+pub fn trace_repeat(starting_value: Option<i64>, values_list: &Vec<i64>, rank: usize) -> Option<FrequencyRepeat> {
+    if rank == 0 || values_list.is_empty() { return None; }
+
+    let changes_count : usize = values_list.len();
+    let mut first_pass : Vec<i64> = vec![starting_value.unwrap_or(0)];
+    for value in values_list.iter() { first_pass.push(first_pass[first_pass.len() - 1].checked_add(*value)?); }
+    let drift : i128 = first_pass[changes_count] as i128 - first_pass[0] as i128;
+    let step : i128 = drift.abs();
+
+    // Frequencies reached by the changes of a pass, congruent modulo the drift (equal without drift), in the order the drift walks them
+    let mut congruent_groups : HashMap<i128, Vec<(i128, usize)>> = HashMap::new();
+    for (index, &frequency) in first_pass[..changes_count].iter().enumerate() {
+        let oriented_frequency : i128 = if step == 0 { frequency as i128 } else { frequency as i128 * drift.signum() };
+        let residue : i128 = if step == 0 { oriented_frequency } else { oriented_frequency.rem_euclid(step) };
+        congruent_groups.entry(residue).or_default().push((oriented_frequency, index));
+    }
+    congruent_groups.values_mut().for_each(|group| group.sort());
+
+    // Pass from which each change reaches frequencies seen before
+    let mut repeat_starts : Vec<(u128, usize)> = Vec::new();
+    for group in congruent_groups.values() {
+        for (position, &(oriented_frequency, index)) in group.iter().enumerate() {
+            let next_position : usize = group.partition_point(|&(other_frequency, _)| other_frequency <= oriented_frequency);
+            if position > 0 && group[position - 1].0 == oriented_frequency { repeat_starts.push((0, index)); }
+            else if step == 0 { repeat_starts.push((1, index)); }
+            else if next_position < group.len() { repeat_starts.push((((group[next_position].0 - oriented_frequency) / step) as u128, index)); }
+        }
+    }
+
+    // Earliest pass by the end of which `rank` repeats happened, the active changes repeating once per pass each
+    let rank : u128 = rank as u128;
+    let repeats_until = |pass: u128| -> u128 {
+        return repeat_starts.iter()
+            .filter(|&&(start_pass, _)| start_pass <= pass)
+            .fold(0, |repeats, &(start_pass, _)| repeats.saturating_add(pass - start_pass + 1));
+    };
+    let first_start : u128 = repeat_starts.iter().map(|&(start_pass, _)| start_pass).min()?;
+    let (mut low_pass, mut high_pass) : (u128, u128) = (first_start, first_start.saturating_add(rank - 1));
+    while low_pass < high_pass {
+        let middle_pass : u128 = low_pass + (high_pass - low_pass) / 2;
+        if repeats_until(middle_pass) >= rank { high_pass = middle_pass; } else { low_pass = middle_pass + 1; }
+    }
+
+    let pass : u128 = low_pass;
+    let previous_repeats : u128 = if pass == 0 { 0 } else { repeats_until(pass - 1) };
+    let mut active_indexes : Vec<usize> = repeat_starts.iter().filter(|&&(start_pass, _)| start_pass <= pass).map(|&(_, index)| index).collect();
+    active_indexes.sort();
+    let index : usize = active_indexes[(rank - previous_repeats - 1) as usize];
+
+    // First reached by the change of the group with the closest frequency behind it (the earliest change among equal ones)
+    let frequency : i64 = i64::try_from(first_pass[index] as i128 + i128::try_from(pass).ok()?.checked_mul(drift)?).ok()?;
+    let group : &Vec<(i128, usize)> = congruent_groups.values().find(|group| group.iter().any(|&(_, member_index)| member_index == index))?;
+    let first_seen_time : u128 = if step == 0 { group[0].1 as u128 } else {
+        let oriented_frequency : i128 = frequency as i128 * drift.signum();
+        let behind_position : usize = group.partition_point(|&(other_frequency, _)| other_frequency <= oriented_frequency) - 1;
+        let earliest_position : usize = group.partition_point(|&(other_frequency, _)| other_frequency < group[behind_position].0);
+        let (behind_frequency, behind_index) : (i128, usize) = group[earliest_position];
+        ((oriented_frequency - behind_frequency) / step) as u128 * changes_count as u128 + behind_index as u128
+    };
+
+    return Some(FrequencyRepeat {
+        frequency: frequency,
+        reached: visit_after(pass * changes_count as u128 + index as u128, changes_count),
+        first_seen: visit_after(first_seen_time, changes_count),
+    });
+}
+
+/// Frequencies reached more than `times` times over the first `passes` passes, starting frequency included, with their number of
+/// visits and sorted by frequency. Visits stop being counted once the frequency leaves the range of `i64`.
+pub fn frequencies_visited_more_than(starting_value: Option<i64>, values_list: &Vec<i64>, times: usize, passes: usize) -> Vec<(i64, usize)> {
     let mut current_value : i64 = starting_value.unwrap_or(0);
-    let mut hash_set : HashSet<i64> = HashSet::new();
+    let mut visits : HashMap<i64, usize> = HashMap::from([(current_value, 1)]);
+    for value in values_list.iter().cycle().take(values_list.len().saturating_mul(passes)) {
+        current_value = match current_value.checked_add(*value) { Some(next_value) => next_value, None => break };
+        let visits_count : &mut usize = visits.entry(current_value).or_insert(0);
+        *visits_count = *visits_count + 1;
+    }
 
-    let mut found_rep : bool = false;
+    let mut frequent : Vec<(i64, usize)> = visits.into_iter().filter(|&(_, count)| count > times).collect();
+    frequent.sort();
+    return frequent;
+}
+
+// ====================================================== SOLUTION IMPLEMENTATION ======================================================
+
+#[derive(Default)]
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY : DayNumber = 1;
+    const TITLE : &'static str = "Chronal Calibration";
+
+    type Input = Vec<i64>;
+    type Part1 = i64;
+    type Part2 = FrequencyRepeat;
+    type Error = Day01Error;
+
+    fn parse(&self, input: &Input) -> Result<Vec<i64>, Day01Error> {
+        return Ok(parse_frequency_changes(input)?);
+    }
+
+    fn part1(&self, values: &Vec<i64>) -> Result<i64, Day01Error> { sum_values(None, values).ok_or(Day01Error::FrequencyOverflow) }
+    fn part2(&self, values: &Vec<i64>) -> Result<FrequencyRepeat, Day01Error> {
+        if values.is_empty() { return Err(Day01Error::NoFrequencyChanges); }
+        sum_values(None, values).ok_or(Day01Error::FrequencyOverflow)?;
+        return trace_first_repeat(None, values).ok_or(Day01Error::NoRepeatedFrequency);
+    }
+
+    fn examples() -> Vec<Example<Day01>> {
+        return vec![
+            Example::new("+1, -2, +3, +1", Day01).part1("3").part2("2"),
+            Example::new("+1, +1, +1", Day01).part1("3"),
+            Example::new("+1, +1, -2", Day01).part1("0"),
+            Example::new("-1, -2, -3", Day01).part1("-6"),
+            Example::new("+1, -1", Day01).part2("0"),
+            Example::new("+3, +3, +4, -2, -4", Day01).part2("10"),
+            Example::new("-6, +3, +8, +5, -6", Day01).part2("5"),
+            Example::new("+7, +7, -2, -7, -4", Day01).part2("14"),
+        ];
+    }
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use proptest::prelude::*;
+
+    fn parse_example(example: &str) -> Vec<i64> {
+        return Day01.parse(&Input::from_string("example", example)).unwrap();
+    }
+
+    #[test]
+    fn sums_frequency_changes() {
+        assert_eq!(Day01.part1(&parse_example("+1, -2, +3, +1")).unwrap(), 3);
+        assert_eq!(Day01.part1(&parse_example("+1, +1, +1")).unwrap(), 3);
+        assert_eq!(Day01.part1(&parse_example("+1, +1, -2")).unwrap(), 0);
+        assert_eq!(Day01.part1(&parse_example("-1, -2, -3")).unwrap(), -6);
+    }
+
+    #[test]
+    fn finds_first_repeated_frequency() {
+        assert_eq!(Day01.part2(&parse_example("+1, -2, +3, +1")).unwrap().frequency, 2);
+        assert_eq!(Day01.part2(&parse_example("+1, -1")).unwrap().frequency, 0);
+        assert_eq!(Day01.part2(&parse_example("+3, +3, +4, -2, -4")).unwrap().frequency, 10);
+        assert_eq!(Day01.part2(&parse_example("-6, +3, +8, +5, -6")).unwrap().frequency, 5);
+        assert_eq!(Day01.part2(&parse_example("+7, +7, -2, -7, -4")).unwrap().frequency, 14);
+    }
+
+    #[test]
+    fn rejects_empty_frequency_changes() {
+        assert!(matches!(Day01.part2(&Vec::new()), Err(Day01Error::NoFrequencyChanges)));
+    }
+
+    #[test]
+    fn detects_frequencies_that_never_repeat() {
+        assert_eq!(first_repeated_current(None, &vec![1, 1]), None);
+        assert_eq!(first_repeated_current(Some(5), &vec![-3, 1]), None);
+        assert!(matches!(Day01.part2(&parse_example("+1, +1")), Err(Day01Error::NoRepeatedFrequency)));
+    }
+
+    #[test]
+    fn finds_repeats_when_the_drift_exceeds_the_frequency_range() {
+        // The pass drifts by 2^64 - 3, so the frequency reached by the first change lands again on the third one on the next pass
+        let values_list : Vec<i64> = vec![-1, i64::MAX, i64::MAX - 1, 1];
+        assert_eq!(first_repeated_current(Some(i64::MIN + 1), &values_list), Some(i64::MAX - 2));
+        assert_eq!(trace_repeat(Some(i64::MIN + 1), &values_list, 1), trace_first_repeat(Some(i64::MIN + 1), &values_list));
+        assert_eq!(trace_first_repeat(Some(i64::MIN + 1), &values_list).unwrap().first_seen, FrequencyVisit { pass: 1, change_index: Some(2) });
+    }
+
+    #[test]
+    fn traces_where_frequencies_repeat() {
+        let repeat : FrequencyRepeat = trace_first_repeat(None, &vec![1, -2, 3, 1]).unwrap();
+        assert_eq!(repeat.reached, FrequencyVisit { pass: 2, change_index: Some(1) });
+        assert_eq!(repeat.first_seen, FrequencyVisit { pass: 1, change_index: Some(2) });
+
+        let repeat : FrequencyRepeat = trace_first_repeat(None, &vec![1, -1]).unwrap();
+        assert_eq!(repeat.reached, FrequencyVisit { pass: 1, change_index: Some(1) });
+        assert_eq!(repeat.first_seen, FrequencyVisit { pass: 1, change_index: None });
+
+        let repeat : FrequencyRepeat = trace_repeat(None, &vec![1, -1], 2).unwrap();
+        assert_eq!((repeat.frequency, repeat.reached, repeat.first_seen.change_index), (1, FrequencyVisit { pass: 2, change_index: Some(0) }, Some(0)));
+        assert_eq!(trace_repeat(None, &vec![1, 1], 1), None);
+    }
+
+    #[test]
+    fn traces_distant_repeats_without_replaying() {
+        let values_list : Vec<i64> = vec![1_000_000_000_000, -999_999_999_999];
+        let repeat : FrequencyRepeat = trace_repeat(None, &values_list, 3).unwrap();
+        assert_eq!(repeat.frequency, 1_000_000_000_002);
+        assert_eq!(repeat.reached, FrequencyVisit { pass: 1_000_000_000_002, change_index: Some(1) });
+        assert_eq!(repeat.first_seen, FrequencyVisit { pass: 3, change_index: Some(0) });
+    }
 
-    while !found_rep {
-        for value in values_list.iter() {
+    #[test]
+    fn counts_frequently_visited_frequencies() {
+        assert_eq!(frequencies_visited_more_than(None, &vec![1, -1], 3, 3), vec![(0, 4)]);
+        assert_eq!(frequencies_visited_more_than(None, &vec![1, -1], 2, 3), vec![(0, 4), (1, 3)]);
+        assert_eq!(frequencies_visited_more_than(Some(3), &vec![1, 1], 1, 5), Vec::new());
+    }
+
+    /// Replays the changes pass after pass, up to `max_passes`.
+    fn replayed_first_repeat(values_list: &Vec<i64>, max_passes: usize) -> Option<i64> {
+        let mut current_value : i64 = 0;
+        let mut hash_set : HashSet<i64> = HashSet::from([current_value]);
+        for value in values_list.iter().cycle().take(values_list.len() * max_passes) {
+            current_value = current_value + value;
+            if !hash_set.insert(current_value) { return Some(current_value); }
+        }
+
+        return None;
+    }
 
+    /// Replays the changes pass after pass up to `max_passes`, tracing the repeat of the given rank.
+    fn replayed_repeat(values_list: &Vec<i64>, rank: usize, max_passes: usize) -> Option<FrequencyRepeat> {
+        let mut current_value : i64 = 0;
+        let mut first_seen : HashMap<i64, u128> = HashMap::from([(current_value, 0)]);
+        let mut repeats_count : usize = 0;
+        for (applied_changes, value) in (1..).zip(values_list.iter().cycle().take(values_list.len() * max_passes)) {
             current_value = current_value + value;
-            if hash_set.contains(&current_value) {
-                found_rep = true;
-                break;
+            match first_seen.get(&current_value) {
+                Some(&first_time) => {
+                    repeats_count = repeats_count + 1;
+                    if repeats_count == rank {
+                        return Some(FrequencyRepeat {
+                            frequency: current_value,
+                            reached: visit_after(applied_changes, values_list.len()),
+                            first_seen: visit_after(first_time, values_list.len()),
+                        });
+                    }
+                },
+                None => { first_seen.insert(current_value, applied_changes); },
             }
-            hash_set.insert(current_value);
         }
+
+        return None;
+    }
+
+    proptest! {
+        #[test]
+        fn analytical_repeat_matches_replay(values_list in prop::collection::vec(-20..=20_i64, 1..30)) {
+            // Frequencies of the first pass stay within 600 of zero, so any repeat happens within 1200 passes
+            prop_assert_eq!(first_repeated_current(None, &values_list), replayed_first_repeat(&values_list, 1_300));
+            for rank in 1..=5 {
+                prop_assert_eq!(trace_repeat(None, &values_list, rank), replayed_repeat(&values_list, rank, 1_300));
+            }
+        }
+    }
+
+    #[test]
+    fn reports_invalid_frequency_change() {
+        let error = Day01.parse(&Input::from_string("example", "+1\n+x\n")).unwrap_err();
+        assert!(matches!(error, Day01Error::Read(ReadError { line: Some(2), column: Some(1), .. })));
+
+        let error = Day01.parse(&Input::from_string("example", "+1, -2\n# calibration\n+3,  4 2\n")).unwrap_err();
+        assert!(matches!(error, Day01Error::Read(ReadError { line: Some(3), column: Some(6), .. })));
+        assert!(Day01.parse(&Input::from_string("example", "+1,, -2")).is_err());
+    }
+
+    #[test]
+    fn parses_flexible_frequency_changes() {
+        let text : &str = "# device 7\n+1, -2,\n\n  +3   # drift\n+1\n";
+        assert_eq!(Day01.parse(&Input::from_string("example", text)).unwrap(), vec![1, -2, 3, 1]);
+        assert_eq!(Day01.parse(&Input::from_string("example", "+1\n-2\n+3\n+1")).unwrap(), vec![1, -2, 3, 1]);
     }
 
-    return current_value;
+    #[test]
+    fn reports_frequency_overflow() {
+        assert_eq!(sum_values(Some(i64::MAX - 1), &vec![1]), Some(i64::MAX));
+        assert!(matches!(Day01.part1(&vec![i64::MAX, 1, -1]), Err(Day01Error::FrequencyOverflow)));
+        assert!(matches!(Day01.part2(&vec![i64::MIN, -1]), Err(Day01Error::FrequencyOverflow)));
+        assert_eq!(trace_repeat(Some(i64::MAX - 2), &vec![1, 0], 3), None);
+
+        // Extreme starting frequencies, whose first pass stays within the range of `i64` while drifting by more than it
+        assert_eq!(first_repeated_current(Some(i64::MIN), &vec![i64::MAX, i64::MAX]), None);
+        assert_eq!(trace_repeat(Some(i64::MIN), &vec![i64::MAX, i64::MAX], 1), None);
+        assert_eq!(trace_first_repeat(Some(i64::MAX), &vec![1, -1]), None);
+        assert_eq!(trace_repeat(Some(i64::MIN + 1), &vec![-1, i64::MAX, i64::MAX - 1, 1], 2), None);
+        assert_eq!(frequencies_visited_more_than(Some(i64::MIN), &vec![i64::MAX, i64::MAX], 0, 3), vec![(i64::MIN, 1), (-1, 1), (i64::MAX - 1, 1)]);
+    }
 }
\ No newline at end of file
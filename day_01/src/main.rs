@@ -1,16 +1,40 @@
-mod read;
-mod lib;
+use common::{CountingAllocator, OutputFormat, Solution, check_example, example_from_args, init_logging, print_json_answers, print_solution_stats, stats_from_args, verbosity_from_args};
+use read::Input;
+use day_01::{Day01, FrequencyRepeat, FrequencyVisit};
 
+#[global_allocator]
+static ALLOCATOR : CountingAllocator = CountingAllocator;
+
+fn describe_visit(visit: &FrequencyVisit) -> String {
+    return match visit.change_index {
+        Some(change_index) => format!("on pass {} by change #{}", visit.pass, change_index),
+        None => "at the start".to_owned(),
+    };
+}
 
 fn main() {
 
-    let values = read::read_int_lines("input.txt".to_owned());
+    init_logging(verbosity_from_args());
+    if let Some(example) = example_from_args().unwrap() {
+        std::process::exit(if check_example::<Day01>(example).unwrap() { 0 } else { 1 });
+    }
+
+    let input = Input::from_path("input.txt").unwrap();
+    if OutputFormat::from_args().unwrap() == OutputFormat::Json {
+        return print_json_answers(&Day01, &input).unwrap();
+    }
+    if stats_from_args() {
+        return print_solution_stats(&Day01, &input).unwrap();
+    }
+
+    let values = Day01.parse(&input).unwrap();
 
     // Part 1
-    let sum_values : i64 = lib::sum_values(None, &values);
+    let sum_values : i64 = Day01.part1(&values).unwrap();
     println!("\r🔊 Resulting frequency: '{}' (Part 1)", sum_values);
     
     // Part 2
-    let repeated_value : i64 = lib::first_repeated_current(None, &values);
-    println!("\r🔊 Repeated frequency: '{}' (Part 2)", repeated_value);
+    let repeat : FrequencyRepeat = Day01.part2(&values).unwrap();
+    println!("\r🔊 Repeated frequency: '{}' (Part 2), reached {} and first seen {}", repeat, describe_visit(&repeat.reached),
+        describe_visit(&repeat.first_seen));
 }
\ No newline at end of file
@@ -1,19 +1,60 @@
 use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use common::{DayNumber, Example, Solution};
+use read::Input;
 
 // ======================================================== STRUCTS DEFINITIONS ========================================================
 
 struct WarehouseBox {
     id: String,
+    letters: Vec<char>,
+}
+
+/// Distance by which box IDs are compared: the number of positions at which they differ (IDs of the same length only), or the
+/// number of letters to insert, delete or replace, swaps of adjacent letters counting as one edit for Damerau-Levenshtein.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DistanceMode {
+    #[default]
+    Hamming,
+    Levenshtein,
+    DamerauLevenshtein,
 }
 
 pub struct Warehouse {
     boxes: Vec<WarehouseBox>,
+    distance_mode: DistanceMode,
+}
+
+#[derive(Serialize)]
+pub struct Checksum {
+    pub number_exact_two: i64,
+    pub number_exact_three: i64,
+}
+
+/// Two boxes whose IDs are at the searched distance, the first box coming first in the warehouse, with the positions of the first ID
+/// changed by the edits (the positions at which both IDs differ, for the Hamming distance).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BoxPair {
+    pub first_id: String,
+    pub second_id: String,
+    pub differing_positions: Vec<usize>,
+}
+
+#[derive(Debug)]
+pub enum Day02Error {
+    NoMatchingBoxes { differences: i64 },
+    UnequalLengths { first_id: String, second_id: String },
 }
 
 // ======================================================== STRUCTS IMPLEMENTATIONS ========================================================
 
 impl WarehouseBox {
-    fn new(id: String) -> WarehouseBox { WarehouseBox { id: id } }
+    fn new(id: String) -> WarehouseBox { WarehouseBox { letters: id.chars().collect(), id: id } }
 
     fn get_count_map(&self) -> HashMap<char, i64> {
         let mut hash_map : HashMap<char, i64> = HashMap::new();
@@ -37,30 +78,113 @@ impl WarehouseBox {
         return false;
     }
 
-    fn get_matched_difference(&self, other_box: &WarehouseBox) -> (i64, String) {
-        let this_id = self.id.chars();
-        let other_id = other_box.id.chars();
+    /// Positions at which two IDs of the same length differ.
+    fn get_differing_positions(&self, other_box: &WarehouseBox) -> Vec<usize> {
+        return (0..self.letters.len())
+            .filter(|&position| self.letters[position] != other_box.letters[position])
+            .collect();
+    }
 
-        let mut current_count : i64 = 0;
-        let mut matched : Vec<char> = Vec::new();
-        for (this_characther, other_characther) in std::iter::zip(this_id, other_id) {
-            if this_characther == other_characther { matched.push(this_characther); }
-            else { current_count = current_count + 1; }
+    /// Edit distance to another ID, swaps of adjacent letters counting as one edit when `transpositions` is set (the optimal string
+    /// alignment variant of Damerau-Levenshtein), along with the positions of this ID that the edits change.
+    fn get_edit_alignment(&self, other_box: &WarehouseBox, transpositions: bool) -> (usize, Vec<usize>) {
+        let (letters_1, letters_2) = (&self.letters, &other_box.letters);
+        let (length_1, length_2) = (letters_1.len(), letters_2.len());
+        let is_swap = |row: usize, column: usize| transpositions && row > 1 && column > 1
+            && letters_1[row - 1] == letters_2[column - 2] && letters_1[row - 2] == letters_2[column - 1];
+
+        let mut costs : Vec<Vec<usize>> = (0..=length_1)
+            .map(|row| (0..=length_2).map(|column| if row == 0 { column } else if column == 0 { row } else { 0 }).collect())
+            .collect();
+        for row in 1..=length_1 {
+            for column in 1..=length_2 {
+                let replacement_cost : usize = costs[row - 1][column - 1] + usize::from(letters_1[row - 1] != letters_2[column - 1]);
+                let mut cost : usize = replacement_cost.min(costs[row - 1][column] + 1).min(costs[row][column - 1] + 1);
+                if is_swap(row, column) { cost = cost.min(costs[row - 2][column - 2] + 1); }
+                costs[row][column] = cost;
+            }
         }
 
-        return (current_count, matched.into_iter().collect());
+        // Walks back through the edits of one of the cheapest alignments
+        let mut changed_positions : Vec<usize> = Vec::new();
+        let (mut row, mut column) : (usize, usize) = (length_1, length_2);
+        while row > 0 || column > 0 {
+            let cost : usize = costs[row][column];
+            if row > 0 && column > 0 && letters_1[row - 1] == letters_2[column - 1] && cost == costs[row - 1][column - 1] {
+                (row, column) = (row - 1, column - 1);
+            } else if row > 0 && column > 0 && cost == costs[row - 1][column - 1] + 1 {
+                changed_positions.push(row - 1);
+                (row, column) = (row - 1, column - 1);
+            } else if is_swap(row, column) && cost == costs[row - 2][column - 2] + 1 {
+                changed_positions.extend([row - 1, row - 2]);
+                (row, column) = (row - 2, column - 2);
+            } else if row > 0 && cost == costs[row - 1][column] + 1 {
+                changed_positions.push(row - 1);
+                row = row - 1;
+            } else {
+                column = column - 1;
+            }
+        }
+
+        changed_positions.reverse();
+        return (costs[length_1][length_2], changed_positions);
+    }
+
+    /// ID without the letters at the given (sorted) positions.
+    fn get_deletion_key(&self, positions: &[usize]) -> Vec<char> {
+        let mut key : Vec<char> = Vec::with_capacity(self.letters.len() - positions.len());
+        let mut remaining_positions = positions.iter().peekable();
+        for (position, &letter) in self.letters.iter().enumerate() {
+            if remaining_positions.next_if_eq(&&position).is_none() { key.push(letter); }
+        }
+
+        return key;
+    }
+}
+
+impl BoxPair {
+    /// Letters both IDs share, in order.
+    pub fn common_letters(&self) -> String {
+        return self.first_id.chars().enumerate()
+            .filter(|(position, _)| self.differing_positions.binary_search(position).is_err())
+            .map(|(_, letter)| letter)
+            .collect();
+    }
+}
+
+impl fmt::Display for Checksum {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}", self.number_exact_two * self.number_exact_three)
+    }
+}
+
+impl fmt::Display for Day02Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day02Error::NoMatchingBoxes { differences } => write!(formatter, "no pair of boxes differs by exactly {} characters", differences),
+            Day02Error::UnequalLengths { first_id, second_id } =>
+                write!(formatter, "the Hamming distance needs IDs of the same length, but '{}' and '{}' differ in length", first_id, second_id),
+        }
     }
 }
 
+impl Error for Day02Error {}
+
 impl Warehouse {
     pub fn new(ids: Vec<String>) -> Warehouse {
         Warehouse {
             boxes: ids.into_iter()
                 .map(|id| WarehouseBox::new(id))
                 .collect(),
+            distance_mode: DistanceMode::Hamming,
         }
     }
 
+    pub fn with_distance_mode(mut self, distance_mode: DistanceMode) -> Warehouse {
+        self.distance_mode = distance_mode;
+        return self;
+    }
+
     pub fn get_number_verify_exact(&self, exact_number: i64) -> i64 {
         let mut current_counter : i64 = 0;
         for warehouse_box in self.boxes.iter() {
@@ -72,16 +196,273 @@ impl Warehouse {
         return current_counter;
     }
 
-    pub fn get_matched_boxes(&self, non_matched_count: i64) -> Option<String> {
-        for (warehouse_box_1_index, warehouse_box_1) in self.boxes.iter().enumerate() {
-            let slice = &self.boxes[(warehouse_box_1_index + 1)..];
-            for warehouse_box_2 in slice.iter() {
+    /// Every pair of boxes whose IDs are at exactly `differences` from each other in the distance mode of the warehouse, in the order
+    /// of the warehouse. The Hamming distance fails on IDs of different lengths instead of comparing them on their common part.
+    pub fn get_pairs_at_distance(&self, differences: usize) -> Result<Vec<BoxPair>, Day02Error> {
+        let mut pairs : Vec<(usize, usize, Vec<usize>)> = match self.distance_mode {
+            DistanceMode::Hamming => self.get_pairs_at_hamming_distance(differences)?,
+            DistanceMode::Levenshtein => self.get_pairs_at_edit_distance(differences, false),
+            DistanceMode::DamerauLevenshtein => self.get_pairs_at_edit_distance(differences, true),
+        };
+
+        pairs.sort();
+        return Ok(pairs.into_iter()
+            .map(|(index_1, index_2, differing_positions)| BoxPair {
+                first_id: self.boxes[index_1].id.clone(),
+                second_id: self.boxes[index_2].id.clone(),
+                differing_positions: differing_positions,
+            })
+            .collect());
+    }
+
+    /// IDs are indexed by deletion keys: two IDs differing at exactly the positions `P` are the only ones to share the key made by
+    /// deleting `P` from them while also differing at all of `P`, so each pair is found once in one bucket instead of comparing them
+    /// all. When there are more such keys per ID than IDs, comparing the IDs pairwise is cheaper and done instead.
+    fn get_pairs_at_hamming_distance(&self, differences: usize) -> Result<Vec<(usize, usize, Vec<usize>)>, Day02Error> {
+        if let Some(first_box) = self.boxes.first() {
+            if let Some(other_box) = self.boxes.iter().find(|other_box| other_box.letters.len() != first_box.letters.len()) {
+                return Err(Day02Error::UnequalLengths { first_id: first_box.id.clone(), second_id: other_box.id.clone() });
+            }
+        }
 
-                let (difference_count, matched) = warehouse_box_1.get_matched_difference(warehouse_box_2);
-                if non_matched_count == difference_count { return Some(matched); }
+        let length : usize = self.boxes.first().map_or(0, |first_box| first_box.letters.len());
+        let mut pairs : Vec<(usize, usize, Vec<usize>)> = Vec::new();
+        if differences > length { return Ok(pairs); }
+
+        if count_combinations(length, differences) > self.boxes.len() {
+            for (index_1, warehouse_box_1) in self.boxes.iter().enumerate() {
+                for (index_2, warehouse_box_2) in self.boxes.iter().enumerate().skip(index_1 + 1) {
+                    let differing_positions : Vec<usize> = warehouse_box_1.get_differing_positions(warehouse_box_2);
+                    if differing_positions.len() == differences { pairs.push((index_1, index_2, differing_positions)); }
+                }
             }
+            return Ok(pairs);
         }
 
-        return None;
+        let mut positions : Vec<usize> = (0..differences).collect();
+        loop {
+            let mut buckets : HashMap<Vec<char>, Vec<usize>> = HashMap::new();
+            for (index, warehouse_box) in self.boxes.iter().enumerate() {
+                buckets.entry(warehouse_box.get_deletion_key(&positions)).or_default().push(index);
+            }
+
+            for bucket in buckets.values().filter(|bucket| bucket.len() > 1) {
+                for (bucket_index, &index_1) in bucket.iter().enumerate() {
+                    for &index_2 in bucket[(bucket_index + 1)..].iter() {
+                        let (letters_1, letters_2) = (&self.boxes[index_1].letters, &self.boxes[index_2].letters);
+                        if positions.iter().all(|&position| letters_1[position] != letters_2[position]) {
+                            pairs.push((index_1, index_2, positions.clone()));
+                        }
+                    }
+                }
+            }
+
+            if !next_combination(&mut positions, length) { break; }
+        }
+
+        return Ok(pairs);
+    }
+
+    /// Compares the IDs pairwise, skipping the ones whose lengths alone already differ by more than `differences`.
+    fn get_pairs_at_edit_distance(&self, differences: usize, transpositions: bool) -> Vec<(usize, usize, Vec<usize>)> {
+        let mut pairs : Vec<(usize, usize, Vec<usize>)> = Vec::new();
+        for (index_1, warehouse_box_1) in self.boxes.iter().enumerate() {
+            for (index_2, warehouse_box_2) in self.boxes.iter().enumerate().skip(index_1 + 1) {
+                if warehouse_box_1.letters.len().abs_diff(warehouse_box_2.letters.len()) > differences { continue; }
+
+                let (distance, changed_positions) = warehouse_box_1.get_edit_alignment(warehouse_box_2, transpositions);
+                if distance == differences { pairs.push((index_1, index_2, changed_positions)); }
+            }
+        }
+
+        return pairs;
+    }
+
+    pub fn get_matched_boxes(&self, non_matched_count: i64) -> Result<Option<String>, Day02Error> {
+        let differences : usize = match usize::try_from(non_matched_count) { Ok(differences) => differences, Err(_) => return Ok(None) };
+        return Ok(self.get_pairs_at_distance(differences)?.first().map(|pair| pair.common_letters()));
+    }
+}
+
+// ======================================================== AUXILIARY FUNCTIONS ========================================================
+
+/// Number of ways to pick `size` positions out of `length`, saturating instead of overflowing.
+fn count_combinations(length: usize, size: usize) -> usize {
+    let mut count : usize = 1;
+    for picked in 0..size.min(length - size) {
+        count = match count.checked_mul(length - picked) { Some(product) => product / (picked + 1), None => return usize::MAX };
+    }
+
+    return count;
+}
+
+/// Moves the sorted positions to the next combination of `0..length` in lexicographic order, `false` after the last one.
+fn next_combination(positions: &mut [usize], length: usize) -> bool {
+    let size : usize = positions.len();
+    for slot in (0..size).rev() {
+        if positions[slot] < length - size + slot {
+            positions[slot] = positions[slot] + 1;
+            for next_slot in (slot + 1)..size { positions[next_slot] = positions[next_slot - 1] + 1; }
+            return true;
+        }
+    }
+
+    return false;
+}
+
+// ====================================================== SOLUTION IMPLEMENTATION ======================================================
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Day02 {
+    pub distance_mode: DistanceMode,
+}
+
+impl Solution for Day02 {
+    const DAY : DayNumber = 2;
+    const TITLE : &'static str = "Inventory Management System";
+
+    type Input = Warehouse;
+    type Part1 = Checksum;
+    type Part2 = String;
+    type Error = Day02Error;
+
+    fn parse(&self, input: &Input) -> Result<Warehouse, Day02Error> {
+        return Ok(Warehouse::new(input.lines()).with_distance_mode(self.distance_mode));
+    }
+
+    fn part1(&self, warehouse: &Warehouse) -> Result<Checksum, Day02Error> {
+        return Ok(Checksum {
+            number_exact_two: warehouse.get_number_verify_exact(2),
+            number_exact_three: warehouse.get_number_verify_exact(3),
+        });
+    }
+
+    fn part2(&self, warehouse: &Warehouse) -> Result<String, Day02Error> {
+        return warehouse.get_matched_boxes(1)?.ok_or(Day02Error::NoMatchingBoxes { differences: 1 });
+    }
+
+    fn examples() -> Vec<Example<Day02>> {
+        return vec![
+            Example::new("abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab", Day02::default()).part1("12"),
+            Example::new("abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz", Day02::default()).part2("fgij"),
+            Example::new("abcde\nfghij\nklmno\npqrs\nfgij", Day02 { distance_mode: DistanceMode::Levenshtein }).part2("fgij"),
+        ];
+    }
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_example(example: &str) -> Warehouse {
+        return Day02::default().parse(&Input::from_string("example", example)).unwrap();
+    }
+
+    #[test]
+    fn computes_checksum() {
+        let warehouse : Warehouse = parse_example("abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab");
+        let checksum : Checksum = Day02::default().part1(&warehouse).unwrap();
+        assert_eq!(checksum.number_exact_two, 4);
+        assert_eq!(checksum.number_exact_three, 3);
+        assert_eq!(checksum.to_string(), "12");
+    }
+
+    #[test]
+    fn finds_common_letters_of_prototype_boxes() {
+        let warehouse : Warehouse = parse_example("abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz");
+        assert_eq!(Day02::default().part2(&warehouse).unwrap(), "fgij");
+    }
+
+    /// Compares every pair of IDs.
+    fn brute_force_pairs(ids: &[String], differences: usize) -> Vec<BoxPair> {
+        let mut pairs : Vec<BoxPair> = Vec::new();
+        for (index_1, id_1) in ids.iter().enumerate() {
+            for id_2 in ids[(index_1 + 1)..].iter() {
+                let differing_positions : Vec<usize> = id_1.chars().zip(id_2.chars()).enumerate()
+                    .filter(|(_, (letter_1, letter_2))| letter_1 != letter_2)
+                    .map(|(position, _)| position)
+                    .collect();
+                if differing_positions.len() == differences {
+                    pairs.push(BoxPair { first_id: id_1.clone(), second_id: id_2.clone(), differing_positions: differing_positions });
+                }
+            }
+        }
+
+        return pairs;
+    }
+
+    #[test]
+    fn finds_all_pairs_at_distance() {
+        let warehouse : Warehouse = parse_example("abcde\nfghij\nabcde\nfguij\nfgxij\nabdde");
+        assert_eq!(warehouse.get_pairs_at_distance(0).unwrap(), vec![
+            BoxPair { first_id: "abcde".to_owned(), second_id: "abcde".to_owned(), differing_positions: vec![] },
+        ]);
+        let pairs : Vec<BoxPair> = warehouse.get_pairs_at_distance(1).unwrap();
+        assert_eq!(pairs.iter().map(|pair| (pair.second_id.as_str(), pair.differing_positions.clone())).collect::<Vec<(&str, Vec<usize>)>>(),
+            vec![("abdde", vec![2]), ("fguij", vec![2]), ("fgxij", vec![2]), ("abdde", vec![2]), ("fgxij", vec![2])]);
+        assert_eq!(pairs[0].common_letters(), "abde");
+        assert_eq!(warehouse.get_pairs_at_distance(6).unwrap(), Vec::new());
+    }
+
+    #[test]
+    fn indexed_search_matches_brute_force() {
+        let text : String = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")).unwrap();
+        let input_ids : Vec<String> = text.lines().map(|id| id.to_owned()).collect();
+        let warehouse : Warehouse = Warehouse::new(input_ids.clone());
+        assert_eq!(warehouse.get_pairs_at_distance(1).unwrap(), brute_force_pairs(&input_ids, 1));
+
+        // Short IDs over a small alphabet, numerous enough to be indexed at every distance and with many pairs at each
+        let mut state : u64 = 7;
+        let short_ids : Vec<String> = (0..300)
+            .map(|_| (0..6).map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                (b'a' + (state >> 61) as u8 % 3) as char
+            }).collect())
+            .collect();
+
+        let warehouse : Warehouse = Warehouse::new(short_ids.clone());
+        for differences in 0..=3 {
+            let pairs : Vec<BoxPair> = warehouse.get_pairs_at_distance(differences).unwrap();
+            assert!(!pairs.is_empty(), "no pairs at distance {}", differences);
+            assert_eq!(pairs, brute_force_pairs(&short_ids, differences), "distance {}", differences);
+        }
+    }
+
+    #[test]
+    fn measures_edit_distances() {
+        let warehouse : Warehouse = Warehouse::new(vec!["kitten".to_owned(), "sitting".to_owned()]).with_distance_mode(DistanceMode::Levenshtein);
+        let pairs : Vec<BoxPair> = warehouse.get_pairs_at_distance(3).unwrap();
+        assert_eq!(pairs[0].differing_positions, vec![0, 4]);
+        assert_eq!(pairs[0].common_letters(), "ittn");
+
+        let ids : Vec<String> = ["abcde", "abxcde", "abdce", "bacde"].iter().map(|id| id.to_string()).collect();
+        let warehouse : Warehouse = Warehouse::new(ids.clone()).with_distance_mode(DistanceMode::Levenshtein);
+        let levenshtein_pairs : Vec<BoxPair> = warehouse.get_pairs_at_distance(1).unwrap();
+        assert_eq!(levenshtein_pairs, vec![
+            BoxPair { first_id: "abcde".to_owned(), second_id: "abxcde".to_owned(), differing_positions: vec![] },
+        ]);
+
+        let damerau_pairs : Vec<BoxPair> = Warehouse::new(ids).with_distance_mode(DistanceMode::DamerauLevenshtein).get_pairs_at_distance(1).unwrap();
+        assert_eq!(damerau_pairs.iter().map(|pair| (pair.second_id.as_str(), pair.differing_positions.clone())).collect::<Vec<(&str, Vec<usize>)>>(),
+            vec![("abxcde", vec![]), ("abdce", vec![2, 3]), ("bacde", vec![0, 1])]);
+    }
+
+    #[test]
+    fn rejects_hamming_distance_on_unequal_lengths() {
+        let warehouse : Warehouse = parse_example("abcde\nfghij\nfgij");
+        assert!(matches!(warehouse.get_pairs_at_distance(1), Err(Day02Error::UnequalLengths { .. })));
+        assert!(matches!(Day02::default().part2(&warehouse), Err(Day02Error::UnequalLengths { .. })));
+
+        let solution : Day02 = Day02 { distance_mode: DistanceMode::Levenshtein };
+        assert_eq!(solution.part2(&solution.parse(&Input::from_string("example", "abcde\nfghij\nfgij")).unwrap()).unwrap(), "fgij");
+    }
+
+    #[test]
+    fn reports_missing_prototype_boxes() {
+        let warehouse : Warehouse = parse_example("abcde\nfghij");
+        assert!(matches!(Day02::default().part2(&warehouse), Err(Day02Error::NoMatchingBoxes { differences: 1 })));
     }
 }
\ No newline at end of file
@@ -1,20 +1,33 @@
-mod read;
-mod lib;
+use common::{CountingAllocator, OutputFormat, Solution, check_example, example_from_args, init_logging, print_json_answers, print_solution_stats, solution_from_args, stats_from_args, verbosity_from_args};
+use read::Input;
+use day_02::Day02;
 
-use lib::Warehouse;
+#[global_allocator]
+static ALLOCATOR : CountingAllocator = CountingAllocator;
 
 fn main() {
 
-    let ids = read::read_lines("input.txt".to_owned());
-    let warehouse = Warehouse::new(ids);
+    init_logging(verbosity_from_args());
+    if let Some(example) = example_from_args().unwrap() {
+        std::process::exit(if check_example::<Day02>(example).unwrap() { 0 } else { 1 });
+    }
+
+    let input = Input::from_path("input.txt").unwrap();
+    let solution : Day02 = solution_from_args().unwrap();
+    if OutputFormat::from_args().unwrap() == OutputFormat::Json {
+        return print_json_answers(&solution, &input).unwrap();
+    }
+    if stats_from_args() {
+        return print_solution_stats(&solution, &input).unwrap();
+    }
+
+    let warehouse = solution.parse(&input).unwrap();
 
     // Part 1
-    let number_exact_two = warehouse.get_number_verify_exact(2);
-    let number_exact_three = warehouse.get_number_verify_exact(3);
-    let checksum = number_exact_two * number_exact_three;
-    println!("\r📦 Warehouse checksum result: '{}' x '{}' = '{}' (Part 1)", number_exact_two, number_exact_three, checksum);
+    let checksum = solution.part1(&warehouse).unwrap();
+    println!("\r📦 Warehouse checksum result: '{}' x '{}' = '{}' (Part 1)", checksum.number_exact_two, checksum.number_exact_three, checksum);
     
     // Part 2
-    let matched_ids = warehouse.get_matched_boxes(1).unwrap();
+    let matched_ids = solution.part2(&warehouse).unwrap();
     println!("\r📦 Warehouse matched: '{}' (Part 2)", matched_ids);
 }
\ No newline at end of file
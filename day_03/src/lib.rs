@@ -1,83 +1,132 @@
 
 
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use common::{BoundingBox, DayNumber, Example, Grid, Image, Point, Solution, heat_color};
+use read::Input;
+
+// ======================================================= CONSTANTS DEFINITIONS =======================================================
+
+/// Square inches the mapping may hold, both over the whole fabric and summed over every claim (the puzzle fabric is 1000x1000).
+const MAX_FABRIC_AREA : i64 = 10_000_000;
+const MAX_CLAIMED_AREA : i64 = 50_000_000;
+
 // ======================================================== STRUCTS DEFINITIONS ========================================================
 
-use std::collections::{HashMap, HashSet};
+pub type Position = Point<i64>;
 
 pub struct Claim {
     id: i64,
-    begin: (i64, i64),
-    size: (i64, i64)
+    begin: Position,
+    size: Position,
+    overlaps: bool
 }
 
+/// Claims over the fabric, mapped as the number of claims of every square inch once developed.
 pub struct FabricManager {
     claims: HashMap<i64, Claim>,
-    current_limits: Option<((i64, i64), (i64, i64))>,
-    current_map: HashMap<(i64, i64), Vec<i64>>
+    current_limits: Option<BoundingBox<i64>>,
+    current_map: Grid<u32>
+}
+
+#[derive(Debug)]
+pub enum Day03Error {
+    InvalidClaim(String),
+    FabricTooLarge,
+    NoClaimWithoutConflicts,
 }
 
 // ======================================================== STRUCTS IMPLEMENTATIONS ========================================================
 
 impl Claim {
-    pub fn new(line: String) -> Claim {
-        let splitted : Vec<String> = line.replace("#", "").replace(" @ ", " ")
+    pub fn new(line: String) -> Result<Claim, Day03Error> {
+        let splitted : Vec<i64> = line.replace("#", "").replace(" @ ", " ")
             .replace(",", " ").replace(": ", " ").replace("x", " ")
-            .split(" ").map(|s| s.to_owned()).collect();
-
-        Claim {
-            id: splitted.get(0).unwrap().parse::<i64>().unwrap(),
-            begin: (splitted.get(1).unwrap().parse::<i64>().unwrap(), splitted.get(2).unwrap().parse::<i64>().unwrap()),
-            size: (splitted.get(3).unwrap().parse::<i64>().unwrap(), splitted.get(4).unwrap().parse::<i64>().unwrap())
+            .split(" ").map(|s| s.parse::<i64>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| Day03Error::InvalidClaim(line.clone()))?;
+        if splitted.len() != 5 { return Err(Day03Error::InvalidClaim(line)); }
+        if splitted[1..].iter().any(|&value| value < 0) { return Err(Day03Error::InvalidClaim(line)); }
+        if splitted[1].checked_add(splitted[3]).is_none() || splitted[2].checked_add(splitted[4]).is_none() {
+            return Err(Day03Error::InvalidClaim(line));
         }
+
+        Ok(Claim {
+            id: splitted[0],
+            begin: Position::new(splitted[1], splitted[2]),
+            size: Position::new(splitted[3], splitted[4]),
+            overlaps: false
+        })
     }
 
     fn get_id(&self) -> i64 { self.id }
-    fn get_claimed_positions(&self) -> Vec<(i64, i64)> {
-        let mut claimed_positions : Vec<(i64, i64)> = Vec::new();
-        for x_var in 0..self.size.0 {
-            for y_var in 0..self.size.1 {
-                claimed_positions.push((self.begin.0 + x_var, self.begin.1 + y_var))
-            }
-        }
+    pub fn get_claimed_area(&self) -> BoundingBox<i64> {
+        return BoundingBox::new(self.begin, self.begin + self.size - Position::new(1, 1));
+    }
+}
 
-        return claimed_positions;
+impl fmt::Display for Day03Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day03Error::InvalidClaim(line) => write!(formatter, "claim '{}' could not be parsed", line),
+            Day03Error::FabricTooLarge => write!(formatter, "claims cover more than {} square inches", MAX_FABRIC_AREA),
+            Day03Error::NoClaimWithoutConflicts => write!(formatter, "every claim overlaps with another one"),
+        }
     }
 }
 
+impl Error for Day03Error {}
+
 impl FabricManager {
     pub fn new(claims: Vec<Claim>) -> FabricManager {
         FabricManager {
             claims: claims.into_iter().map(|claim| (claim.get_id(), claim)).collect(),
             current_limits: None,
-            current_map: HashMap::new(),
+            current_map: Grid::new(BoundingBox::new(Position::new(0, 0), Position::new(-1, -1)), 0),
         }
     }
 
-    pub fn develop_mapping(&mut self) {
-        for (claim_id, claim) in self.claims.iter() {
-            for claimed_position in claim.get_claimed_positions() {
-
-                // Update Limits
-                if self.current_limits.is_none() { self.current_limits = Some((claimed_position, claimed_position)) }
-                
-                let mut limits = self.current_limits.unwrap(); 
-                if limits.0.0 > claimed_position.0 { limits.0.0 = claimed_position.0; }
-                if limits.0.1 > claimed_position.1 { limits.0.1 = claimed_position.1; }
-                if limits.1.0 < claimed_position.0 { limits.1.0 = claimed_position.0; }
-                if limits.1.1 < claimed_position.1 { limits.1.1 = claimed_position.1; }
-                self.current_limits = Some(limits);
-                
-                // Add results to mapping
-                if !self.current_map.contains_key(&claimed_position) { self.current_map.insert(claimed_position, Vec::new()); }
-                self.current_map.get_mut(&claimed_position).unwrap().push(*claim_id);
+    pub fn develop_mapping(&mut self) -> Result<(), Day03Error> {
+        let mut claimed_area_sum : i64 = 0;
+        for claim in self.claims.values() {
+
+            // Update Limits
+            let claimed_area : BoundingBox<i64> = claim.get_claimed_area();
+            if claimed_area.is_empty() { continue; }
+            claimed_area_sum = claimed_area.width().checked_mul(claimed_area.height())
+                .and_then(|area| claimed_area_sum.checked_add(area))
+                .filter(|&area| area <= MAX_CLAIMED_AREA)
+                .ok_or(Day03Error::FabricTooLarge)?;
+            match self.current_limits.as_mut() {
+                Some(limits) => { limits.include(&claimed_area.min); limits.include(&claimed_area.max); },
+                None => self.current_limits = Some(claimed_area),
             }
         }
+
+        // Add results to mapping
+        let Some(limits) = self.current_limits else { return Ok(()); };
+        if limits.width().checked_mul(limits.height()).is_none_or(|area| area > MAX_FABRIC_AREA) { return Err(Day03Error::FabricTooLarge); }
+        self.current_map = Grid::new(limits, 0);
+        for claim in self.claims.values() {
+            for claimed_position in claim.get_claimed_area().points() {
+                self.current_map[claimed_position] = self.current_map[claimed_position].saturating_add(1);
+            }
+        }
+
+        // Flag the claims sharing any square inch with another one
+        for claim in self.claims.values_mut() {
+            claim.overlaps = claim.get_claimed_area().points().any(|claimed_position| self.current_map[claimed_position] >= 2);
+        }
+
+        return Ok(());
     }
 
     pub fn check_mapping_position_equal_or_higher(&self, value_to_check: usize) -> i64 {
         let mut current_counter : i64 = 0;
-        for (_, claims) in self.current_map.iter() {
-            if claims.len() >= value_to_check {
+        for &claims_count in self.current_map.values() {
+            if claims_count > 0 && claims_count as usize >= value_to_check {
                 current_counter = current_counter + 1;
             }
         }
@@ -86,21 +135,105 @@ impl FabricManager {
     }
 
     pub fn get_claim_without_conflicts(&self) -> Option<i64> {
-        let mut claims_with_conflicts : HashSet<i64> = HashSet::new();
-        for (_, claims) in self.current_map.iter() {
-            if claims.len() >= 2 {
-                for claim in claims.iter() {
-                    claims_with_conflicts.insert(*claim);
-                }
-            }
-        }
+        return self.claims.values()
+            .find(|claim| !claim.overlaps)
+            .map(|claim| claim.get_id());
+    }
 
-        for (claim_id, _) in self.claims.iter() {
-            if !claims_with_conflicts.contains(claim_id) {
-                return Some(*claim_id);
-            }
-        }
-        
-        return None;
+    /// Heatmap of the number of claims over every square inch of the fabric, overlaps standing out as the hottest areas.
+    pub fn render_heatmap(&self) -> Image {
+        let max_claims : u32 = self.current_map.values().copied().max().unwrap_or(0);
+        return Image::from_grid(&self.current_map, |_, &claims_count| heat_color(claims_count as f64 / max_claims.max(1) as f64));
+    }
+}
+
+// ============================================================= EXAMPLES ==============================================================
+
+const EXAMPLE : &str = "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2";
+
+// ====================================================== SOLUTION IMPLEMENTATION ======================================================
+
+#[derive(Default)]
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY : DayNumber = 3;
+    const TITLE : &'static str = "No Matter How You Slice It";
+
+    type Input = FabricManager;
+    type Part1 = i64;
+    type Part2 = i64;
+    type Error = Day03Error;
+
+    fn parse(&self, input: &Input) -> Result<FabricManager, Day03Error> {
+        let claims : Vec<Claim> = input.lines().into_iter()
+            .map(|code| Claim::new(code))
+            .collect::<Result<_, _>>()?;
+        let mut fabric_manager : FabricManager = FabricManager::new(claims);
+        fabric_manager.develop_mapping()?;
+
+        return Ok(fabric_manager);
+    }
+
+    fn part1(&self, fabric_manager: &FabricManager) -> Result<i64, Day03Error> {
+        return Ok(fabric_manager.check_mapping_position_equal_or_higher(2));
+    }
+
+    fn part2(&self, fabric_manager: &FabricManager) -> Result<i64, Day03Error> {
+        return fabric_manager.get_claim_without_conflicts().ok_or(Day03Error::NoClaimWithoutConflicts);
+    }
+
+    fn render(&self, fabric_manager: &FabricManager) -> Result<Vec<Image>, Day03Error> {
+        return Ok(vec![fabric_manager.render_heatmap()]);
+    }
+
+    fn examples() -> Vec<Example<Day03>> {
+        return vec![
+            Example::new(EXAMPLE, Day03).part1("4").part2("3"),
+        ];
+    }
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_overlapping_square_inches() {
+        let fabric_manager : FabricManager = Day03.parse(&Input::from_string("example", EXAMPLE)).unwrap();
+        assert_eq!(Day03.part1(&fabric_manager).unwrap(), 4);
+    }
+
+    #[test]
+    fn finds_claim_without_overlaps() {
+        let fabric_manager : FabricManager = Day03.parse(&Input::from_string("example", EXAMPLE)).unwrap();
+        assert_eq!(Day03.part2(&fabric_manager).unwrap(), 3);
+    }
+
+    #[test]
+    fn rejects_malformed_claim() {
+        assert!(matches!(Claim::new("#1 @ 1,3".to_owned()), Err(Day03Error::InvalidClaim(_))));
+        assert!(matches!(Claim::new("#1 @ a,3: 4x4".to_owned()), Err(Day03Error::InvalidClaim(_))));
+        assert!(matches!(Claim::new("#1 @ -1,3: 4x4".to_owned()), Err(Day03Error::InvalidClaim(_))));
+        assert!(matches!(Claim::new("#1 @ 9223372036854775807,3: 4x4".to_owned()), Err(Day03Error::InvalidClaim(_))));
+    }
+
+    #[test]
+    fn rejects_oversized_fabric() {
+        let input : Input = Input::from_string("huge", "#1 @ 0,0: 1x1\n#2 @ 900000000,900000000: 1x1");
+        assert!(matches!(Day03.parse(&input), Err(Day03Error::FabricTooLarge)));
+        let input : Input = Input::from_string("huge", "#1 @ 0,0: 100000x100000");
+        assert!(matches!(Day03.parse(&input), Err(Day03Error::FabricTooLarge)));
+    }
+
+    #[test]
+    fn renders_claim_heatmap() {
+        let fabric_manager : FabricManager = Day03.parse(&Input::from_string("example", EXAMPLE)).unwrap();
+        let image : Image = fabric_manager.render_heatmap();
+        assert_eq!((image.get_width(), image.get_height()), (6, 6));
+        assert_eq!(image.get_pixel(2, 2), Some(heat_color(1.0)));
+        assert_eq!(image.get_pixel(0, 0), Some(heat_color(0.0)));
     }
 }
\ No newline at end of file
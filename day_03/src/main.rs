@@ -1,22 +1,37 @@
-mod read;
-mod lib;
+use common::{CountingAllocator, OutputFormat, Solution, check_example, example_from_args, init_logging, print_json_answers, print_solution_stats, render_path_from_args, save_render, stats_from_args, verbosity_from_args};
+use read::Input;
+use day_03::Day03;
 
-use lib::{Claim, FabricManager};
+#[global_allocator]
+static ALLOCATOR : CountingAllocator = CountingAllocator;
 
 fn main() {
 
-    let claim_codes = read::read_lines("input.txt".to_owned());
-    let claims : Vec<Claim> = claim_codes.into_iter().map(|code| Claim::new(code)).collect();
-    let mut fabric_manager : FabricManager = FabricManager::new(claims);
+    init_logging(verbosity_from_args());
+    if let Some(example) = example_from_args().unwrap() {
+        std::process::exit(if check_example::<Day03>(example).unwrap() { 0 } else { 1 });
+    }
 
-    fabric_manager.develop_mapping();
+    let input = Input::from_path("input.txt").unwrap();
+    if let Some(render_path) = render_path_from_args().unwrap() {
+        for frame_path in save_render(&Day03, &input, &render_path).unwrap() {
+            eprintln!("🖼️  Rendered '{}'", frame_path.display());
+        }
+    }
+    if OutputFormat::from_args().unwrap() == OutputFormat::Json {
+        return print_json_answers(&Day03, &input).unwrap();
+    }
+    if stats_from_args() {
+        return print_solution_stats(&Day03, &input).unwrap();
+    }
 
+    let fabric_manager = Day03.parse(&input).unwrap();
 
     // Part 1
-    let number_of_conflict_positions = fabric_manager.check_mapping_position_equal_or_higher(2);
+    let number_of_conflict_positions = Day03.part1(&fabric_manager).unwrap();
     println!("\r👔 Number of positions in conflict: '{}' (Part 1)", number_of_conflict_positions);
     
     // Part 2
-    let claim_without_conflict = fabric_manager.get_claim_without_conflicts().unwrap();
+    let claim_without_conflict = Day03.part2(&fabric_manager).unwrap();
     println!("\r👔 Claim without conflicts: '{}' (Part 2)", claim_without_conflict);
 }
\ No newline at end of file
@@ -1,13 +1,26 @@
 
 use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::LazyLock;
 
 use chrono::{NaiveDateTime, Timelike};
 use regex::Regex;
+use serde::Serialize;
+
+use common::{DayNumber, Example, Solution};
+use read::Input;
 
 // ======================================================= CONSTANTS DEFINITIONS =======================================================
 
 const TIMESTAMP_FORMAT : &str = "%Y-%m-%d %H:%M";
 
+// Compiled once, as compiling them for every log made long inputs crawl
+static TIMESTAMP_REGEX : LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\[(\d{4}-\d{2}-\d{2} \d{2}:\d{2})\]").unwrap());
+static START_SHIFT_REGEX : LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\[\d{4}-\d{2}-\d{2} \d{2}:\d{2}\] Guard #(\d+) begins shift").unwrap());
+static FALL_ASLEEP_REGEX : LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\[\d{4}-\d{2}-\d{2} \d{2}:\d{2}\] falls asleep").unwrap());
+static WAKE_UP_REGEX : LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\[\d{4}-\d{2}-\d{2} \d{2}:\d{2}\] wakes up").unwrap());
+
 // ======================================================== STRUCTS DEFINITIONS ========================================================
 
 #[derive(Debug)]
@@ -28,69 +41,99 @@ pub struct Scheduler {
     logs: Vec<Log>
 }
 
+#[derive(Serialize)]
+pub struct GuardCode {
+    pub guard: i64,
+    pub minute: i64,
+}
+
+#[derive(Debug)]
+pub enum Day04Error {
+    InvalidLog(String),
+    LogWithoutGuard(NaiveDateTime),
+    UnprocessedLogs,
+    NoGuardAsleep,
+}
+
 // ======================================================== STRUCTS IMPLEMENTATIONS ========================================================
 
 impl Log {
 
-    pub fn new(log_string: String) -> Log {
-
-        let timestamp_regex : Regex = Regex::new(r"\[(\d{4}-\d{2}-\d{2} \d{2}:\d{2})\]").unwrap();
-        let start_shift_regex : Regex = Regex::new(r"\[\d{4}-\d{2}-\d{2} \d{2}:\d{2}\] Guard #(\d+) begins shift").unwrap();
-        let fall_asleep_regex : Regex = Regex::new(r"\[\d{4}-\d{2}-\d{2} \d{2}:\d{2}\] falls asleep").unwrap();
-        let wake_up_regex : Regex = Regex::new(r"\[\d{4}-\d{2}-\d{2} \d{2}:\d{2}\] wakes up").unwrap();
+    pub fn new(log_string: String) -> Result<Log, Day04Error> {
 
-        let timestamp_string = timestamp_regex.captures(&log_string)
-            .unwrap().get(1)
-            .unwrap().as_str();
+        let timestamp_string = TIMESTAMP_REGEX.captures(&log_string)
+            .and_then(|captures| captures.get(1))
+            .ok_or_else(|| Day04Error::InvalidLog(log_string.clone()))?.as_str();
         let timestamp = NaiveDateTime::parse_from_str(timestamp_string, TIMESTAMP_FORMAT)
-            .unwrap();
+            .map_err(|_| Day04Error::InvalidLog(log_string.clone()))?;
 
         let mut guard : Option<i64> = None;
         let log_type : LogType;
 
-        if start_shift_regex.is_match(&log_string) {
-            let guard_string = start_shift_regex.captures(&log_string)
+        if START_SHIFT_REGEX.is_match(&log_string) {
+            let guard_string = START_SHIFT_REGEX.captures(&log_string)
                 .unwrap().get(1)
                 .unwrap().as_str();
-            guard = Some(guard_string.parse::<i64>().unwrap());
+            guard = Some(guard_string.parse::<i64>().map_err(|_| Day04Error::InvalidLog(log_string.clone()))?);
             log_type = LogType::StartShift;
 
-        } else if fall_asleep_regex.is_match(&log_string) { log_type = LogType::FallAsleep; } 
-        else if wake_up_regex.is_match(&log_string) { log_type = LogType::WakeUp; }
-        else { panic!("🚨 Log could not be parsed into one of the specified types!")}
+        } else if FALL_ASLEEP_REGEX.is_match(&log_string) { log_type = LogType::FallAsleep; } 
+        else if WAKE_UP_REGEX.is_match(&log_string) { log_type = LogType::WakeUp; }
+        else { return Err(Day04Error::InvalidLog(log_string)); }
 
-
-        Log {
+        Ok(Log {
             guard: guard,
             timestamp: timestamp,
             log_type: log_type,
+        })
+    }
+}
+
+impl fmt::Display for GuardCode {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}", self.guard * self.minute)
+    }
+}
+
+impl fmt::Display for Day04Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day04Error::InvalidLog(log) => write!(formatter, "log '{}' could not be parsed into one of the specified types", log),
+            Day04Error::LogWithoutGuard(timestamp) => write!(formatter, "log at '{}' happens before any guard begins a shift", timestamp),
+            Day04Error::UnprocessedLogs => write!(formatter, "logs must be processed before being analysed"),
+            Day04Error::NoGuardAsleep => write!(formatter, "no guard has ever fallen asleep"),
         }
     }
 }
 
+impl Error for Day04Error {}
+
 impl Scheduler {
     pub fn new(mut logs: Vec<Log>) -> Scheduler {
         logs.sort_by_key(|log| log.timestamp);
         Scheduler { logs: logs }
     }
 
-    pub fn process_log_guards(&mut self) {
+    pub fn process_log_guards(&mut self) -> Result<(), Day04Error> {
         let mut current_guard : Option<i64> = None;
         for log in self.logs.iter_mut() {
             match log.log_type {
                 LogType::StartShift => current_guard = log.guard,
+                LogType::FallAsleep | LogType::WakeUp if current_guard.is_none() => return Err(Day04Error::LogWithoutGuard(log.timestamp)),
                 LogType::FallAsleep => log.guard = current_guard,
                 LogType::WakeUp => log.guard = current_guard,
             }
         }
+
+        return Ok(());
     }
 
-    pub fn get_most_sleepy_guard(&self) -> Option<i64> {
+    pub fn get_most_sleepy_guard(&self) -> Result<i64, Day04Error> {
         let mut map_sleep_durations : HashMap<i64, i64> = HashMap::new();
         let mut current_guard_start : Option<(i64, Option<NaiveDateTime>)> = None;
 
         for log in self.logs.iter() {
-            if log.guard.is_none() { panic!("🚨 Please make sure that logs have been processed!"); }
+            if log.guard.is_none() { return Err(Day04Error::UnprocessedLogs); }
             match log.log_type {
                 LogType::StartShift => current_guard_start = None,
                 LogType::FallAsleep if current_guard_start.is_none() => current_guard_start = Some((log.guard.unwrap(), Some(log.timestamp))),
@@ -109,15 +152,16 @@ impl Scheduler {
 
         return map_sleep_durations.into_iter()
             .max_by_key(|(_, count)| *count)
-            .map(|(guard, _)| guard);
+            .map(|(guard, _)| guard)
+            .ok_or(Day04Error::NoGuardAsleep);
     }
 
-    pub fn get_most_probable_sleep_time(&self, guard_id: i64) -> Option<i64> {
+    pub fn get_most_probable_sleep_time(&self, guard_id: i64) -> Result<i64, Day04Error> {
         let mut map_scheduled_minutes : HashMap<i64, i64> = HashMap::new();
         let mut current_guard_start : Option<(i64, Option<NaiveDateTime>)> = None;
 
         for log in self.logs.iter() {
-            if log.guard.is_none() { panic!("🚨 Please make sure that logs have been processed!"); }
+            if log.guard.is_none() { return Err(Day04Error::UnprocessedLogs); }
             if log.guard.unwrap() != guard_id { continue; }
             match log.log_type {
                 LogType::StartShift => current_guard_start = None,
@@ -140,15 +184,16 @@ impl Scheduler {
 
         return map_scheduled_minutes.into_iter()
             .max_by_key(|(_, count)| *count)
-            .map(|(minute, _)| minute);
+            .map(|(minute, _)| minute)
+            .ok_or(Day04Error::NoGuardAsleep);
     }
 
-    pub fn get_most_probable_sleep_time_all(&self) -> Option<(i64, i64)> {
+    pub fn get_most_probable_sleep_time_all(&self) -> Result<(i64, i64), Day04Error> {
         let mut map_scheduled_minutes : HashMap<i64, HashMap<i64, i64>> = HashMap::new();
         let mut current_guard_start : Option<(i64, Option<NaiveDateTime>)> = None;
 
         for log in self.logs.iter() {
-            if log.guard.is_none() { panic!("🚨 Please make sure that logs have been processed!"); }
+            if log.guard.is_none() { return Err(Day04Error::UnprocessedLogs); }
             if !map_scheduled_minutes.contains_key(&log.guard.unwrap()) { map_scheduled_minutes.insert(log.guard.unwrap(), HashMap::new()); }
             match log.log_type {
                 LogType::StartShift => current_guard_start = None,
@@ -178,6 +223,107 @@ impl Scheduler {
                 return (guard, max_by_guard.0, max_by_guard.1);
             })
             .max_by_key(|(_, _, count)| *count)
-            .map(|(guard, minute, _)| (guard, minute));
+            .map(|(guard, minute, _)| (guard, minute))
+            .ok_or(Day04Error::NoGuardAsleep);
+    }
+}
+
+// ============================================================= EXAMPLES ==============================================================
+
+const EXAMPLE : &str = "\
+[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-05 00:55] wakes up";
+
+// ====================================================== SOLUTION IMPLEMENTATION ======================================================
+
+#[derive(Default)]
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY : DayNumber = 4;
+    const TITLE : &'static str = "Repose Record";
+
+    type Input = Scheduler;
+    type Part1 = GuardCode;
+    type Part2 = GuardCode;
+    type Error = Day04Error;
+
+    fn parse(&self, input: &Input) -> Result<Scheduler, Day04Error> {
+        let logs : Vec<Log> = input.lines().into_iter()
+            .map(|log_string| Log::new(log_string))
+            .collect::<Result<_, _>>()?;
+        let mut scheduler : Scheduler = Scheduler::new(logs);
+        scheduler.process_log_guards()?;
+
+        return Ok(scheduler);
+    }
+
+    fn part1(&self, scheduler: &Scheduler) -> Result<GuardCode, Day04Error> {
+        let guard_most_sleepy = scheduler.get_most_sleepy_guard()?;
+        let guard_most_probable = scheduler.get_most_probable_sleep_time(guard_most_sleepy)?;
+        return Ok(GuardCode { guard: guard_most_sleepy, minute: guard_most_probable });
+    }
+
+    fn part2(&self, scheduler: &Scheduler) -> Result<GuardCode, Day04Error> {
+        let (guard_most_sleepy, guard_most_probable) = scheduler.get_most_probable_sleep_time_all()?;
+        return Ok(GuardCode { guard: guard_most_sleepy, minute: guard_most_probable });
+    }
+
+    fn examples() -> Vec<Example<Day04>> {
+        return vec![
+            Example::new(EXAMPLE, Day04).part1("240").part2("4455"),
+        ];
+    }
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_example() -> Scheduler {
+        return Day04.parse(&Input::from_string("example", EXAMPLE)).unwrap();
+    }
+
+    #[test]
+    fn applies_first_strategy() {
+        let guard_code : GuardCode = Day04.part1(&parse_example()).unwrap();
+        assert_eq!((guard_code.guard, guard_code.minute), (10, 24));
+        assert_eq!(guard_code.to_string(), "240");
+    }
+
+    #[test]
+    fn applies_second_strategy() {
+        let guard_code : GuardCode = Day04.part2(&parse_example()).unwrap();
+        assert_eq!((guard_code.guard, guard_code.minute), (99, 45));
+        assert_eq!(guard_code.to_string(), "4455");
+    }
+
+    #[test]
+    fn rejects_unknown_log_types() {
+        assert!(matches!(Log::new("[1518-11-01 00:00] Guard #10 takes a nap".to_owned()), Err(Day04Error::InvalidLog(_))));
+        assert!(matches!(Log::new("Guard #10 begins shift".to_owned()), Err(Day04Error::InvalidLog(_))));
+    }
+
+    #[test]
+    fn rejects_logs_before_any_shift() {
+        let input : Input = Input::from_string("example", "[1518-11-01 00:05] falls asleep");
+        assert!(matches!(Day04.parse(&input), Err(Day04Error::LogWithoutGuard(_))));
     }
 }
\ No newline at end of file
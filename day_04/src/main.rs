@@ -1,23 +1,32 @@
-mod read;
-mod lib;
+use common::{CountingAllocator, OutputFormat, Solution, check_example, example_from_args, init_logging, print_json_answers, print_solution_stats, stats_from_args, verbosity_from_args};
+use read::Input;
+use day_04::Day04;
 
-use lib::{Log, Scheduler};
+#[global_allocator]
+static ALLOCATOR : CountingAllocator = CountingAllocator;
 
 fn main() {
 
-    let logs_string = read::read_lines("input.txt".to_owned());
-    let logs : Vec<Log> = logs_string.into_iter()
-        .map(|log_string| Log::new(log_string)).collect();
+    init_logging(verbosity_from_args());
+    if let Some(example) = example_from_args().unwrap() {
+        std::process::exit(if check_example::<Day04>(example).unwrap() { 0 } else { 1 });
+    }
 
-    let mut scheduler : Scheduler = Scheduler::new(logs);
-    scheduler.process_log_guards();
+    let input = Input::from_path("input.txt").unwrap();
+    if OutputFormat::from_args().unwrap() == OutputFormat::Json {
+        return print_json_answers(&Day04, &input).unwrap();
+    }
+    if stats_from_args() {
+        return print_solution_stats(&Day04, &input).unwrap();
+    }
+
+    let scheduler = Day04.parse(&input).unwrap();
 
     // Part 1
-    let guard_most_sleepy = scheduler.get_most_sleepy_guard().unwrap();
-    let guard_most_probable = scheduler.get_most_probable_sleep_time(guard_most_sleepy).unwrap();
-    println!("\r👮 Code according to Strategy 1: '{}' x '{}' = '{}' (Part 1)", guard_most_sleepy, guard_most_probable, guard_most_sleepy * guard_most_probable);
+    let guard_code = Day04.part1(&scheduler).unwrap();
+    println!("\r👮 Code according to Strategy 1: '{}' x '{}' = '{}' (Part 1)", guard_code.guard, guard_code.minute, guard_code);
     
     // Part 2
-    let (guard_most_sleepy, guard_most_probable) = scheduler.get_most_probable_sleep_time_all().unwrap();
-    println!("\r👮 Code according to Strategy 2: '{}' x '{}' = '{}' (Part 2)", guard_most_sleepy, guard_most_probable, guard_most_sleepy * guard_most_probable);
+    let guard_code = Day04.part2(&scheduler).unwrap();
+    println!("\r👮 Code according to Strategy 2: '{}' x '{}' = '{}' (Part 2)", guard_code.guard, guard_code.minute, guard_code);
 }
\ No newline at end of file
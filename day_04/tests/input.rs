@@ -0,0 +1,22 @@
+use std::process::{Command, Output};
+
+#[test]
+fn solves_checked_in_input() {
+    let output : Output = Command::new(env!("CARGO_BIN_EXE_day_04")).current_dir(env!("CARGO_MANIFEST_DIR")).output().unwrap();
+    let stdout : String = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.contains("'14346' (Part 1)"), "unexpected part 1 output: {}", stdout);
+    assert!(stdout.contains("'5705' (Part 2)"), "unexpected part 2 output: {}", stdout);
+}
+
+#[test]
+fn solves_checked_in_input_as_json() {
+    let output : Output = Command::new(env!("CARGO_BIN_EXE_day_04")).args(["--format", "json"]).current_dir(env!("CARGO_MANIFEST_DIR")).output().unwrap();
+    let stdout : String = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.starts_with(r#"[{"day":4,"part":1,"answer":"14346","elapsed_ms":"#), "unexpected output: {}", stdout);
+    assert!(stdout.contains(r#""extra":{"guard":797,"minute":18}"#), "unexpected part 1 output: {}", stdout);
+    assert!(stdout.contains(r#""extra":{"guard":163,"minute":35}"#), "unexpected part 2 output: {}", stdout);
+}
\ No newline at end of file
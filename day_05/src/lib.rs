@@ -1,4 +1,11 @@
 use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+
+use serde::Serialize;
+
+use common::{DayNumber, Example, Solution};
+use read::{Input, ReadError};
 
 // ======================================================= CONSTANTS DEFINITIONS =======================================================
 
@@ -22,26 +29,43 @@ pub struct ProblematicPolymer {
     states: HashMap<PolymerUnitType, (Vec<PolymerUnit>, usize)>
 }
 
+#[derive(Serialize)]
+pub struct LessProblematic {
+    pub removed_type: char,
+    pub units: usize,
+}
+
+#[derive(Debug)]
+pub enum Day05Error {
+    Read(ReadError),
+    InvalidUnit { unit: char, position: usize },
+    NoUnitTypes,
+}
+
 // ======================================================== STRUCTS IMPLEMENTATIONS ========================================================
 
 impl PolymerUnit {
+    pub fn new(unit: char, position: usize) -> Result<PolymerUnit, Day05Error> {
+        if !unit.is_lowercase() && !unit.is_uppercase() { return Err(Day05Error::InvalidUnit { unit: unit, position: position }); }
+        return Ok(PolymerUnit(unit));
+    }
+
     pub fn get_char(&self) -> char { return self.0; }
     fn get_polarity(&self) -> PolymerUnitPolarity {
         if self.get_char().is_lowercase() { return PolymerUnitPolarity::Lowercase; }
-        else if self.get_char().is_uppercase() { return PolymerUnitPolarity::Uppercase; }
-        else { panic!("🚨 Polarity for '{}' could not be established!", self.get_char()) }
+        else { return PolymerUnitPolarity::Uppercase; }
     }
     fn get_type(&self) -> PolymerUnitType { self.get_char().to_uppercase().nth(0).unwrap() }
 }
 
 impl Polymer {
-    pub fn new(initial_state_chars: &Vec<char>) -> Polymer {
-        Polymer {
-            initial_state: initial_state_chars.into_iter()
-                .map(|char| PolymerUnit(*char))
-                .collect(),
+    pub fn new(initial_state_chars: &Vec<char>) -> Result<Polymer, Day05Error> {
+        Ok(Polymer {
+            initial_state: initial_state_chars.iter().enumerate()
+                .map(|(position, char)| PolymerUnit::new(*char, position))
+                .collect::<Result<_, _>>()?,
             states: Vec::new()
-        }
+        })
     }
 
     pub fn new_from_polymers(initial_state: Vec<PolymerUnit>) -> Polymer {
@@ -73,7 +97,7 @@ impl Polymer {
             }
         }
 
-        if !last_changed { new_polymer.push(start_point.last().unwrap().clone()); }
+        if !last_changed { new_polymer.extend(start_point.last().cloned()); }
         if some_changed { self.states.push(new_polymer); }
         return some_changed;
     }
@@ -81,23 +105,52 @@ impl Polymer {
     pub fn get_last_iteration(&self) -> &Vec<PolymerUnit> { self.states.last().unwrap_or(&self.initial_state) }
 }
 
+impl fmt::Display for LessProblematic {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}", self.units)
+    }
+}
+
+impl fmt::Display for Day05Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day05Error::Read(error) => write!(formatter, "{}", error),
+            Day05Error::InvalidUnit { unit, position } => write!(formatter, "polarity for '{}' at position {} could not be established", unit, position),
+            Day05Error::NoUnitTypes => write!(formatter, "polymer has no unit types to be removed"),
+        }
+    }
+}
+
+impl Error for Day05Error {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Day05Error::Read(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ReadError> for Day05Error {
+    fn from(error: ReadError) -> Day05Error { Day05Error::Read(error) }
+}
+
 impl ProblematicPolymer {
-    pub fn new(initial_state_chars: &Vec<char>) -> ProblematicPolymer {
+    pub fn new(initial_state_chars: &Vec<char>) -> Result<ProblematicPolymer, Day05Error> {
 
-        let initial_state : Vec<PolymerUnit> = initial_state_chars.into_iter()
-            .map(|char| PolymerUnit(*char))
-            .collect();
+        let initial_state : Vec<PolymerUnit> = initial_state_chars.iter().enumerate()
+            .map(|(position, char)| PolymerUnit::new(*char, position))
+            .collect::<Result<_, _>>()?;
         let types : Vec<PolymerUnitType> = initial_state.iter()
             .map(|unit| unit.get_type())
             .collect::<HashSet<_>>()
             .into_iter()
             .collect();
 
-        ProblematicPolymer { 
+        Ok(ProblematicPolymer { 
             initial_state: initial_state,
             polymer_types: types,
             states: HashMap::new(),
-        }
+        })
     }
 
     pub fn run_polymers(&mut self) {
@@ -105,7 +158,7 @@ impl ProblematicPolymer {
         for polymer_type_remove in self.polymer_types.iter() {
             let new_initial_state : Vec<PolymerUnit> = self.initial_state.iter()
                 .filter(|&unit| unit.get_type() != *polymer_type_remove)
-                .map(|unit| unit.clone())
+                .cloned()
                 .collect();
 
             let mut new_polymer : Polymer = Polymer::new_from_polymers(new_initial_state);
@@ -125,4 +178,79 @@ impl ProblematicPolymer {
             .min_by_key(|(_, _, &polymer_size)| polymer_size)
             .map(|(removed_unit, final_polymer, &polymer_size)| (*removed_unit, final_polymer.clone(), polymer_size))
     }
+}
+
+// ====================================================== SOLUTION IMPLEMENTATION ======================================================
+
+#[derive(Default)]
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY : DayNumber = 5;
+    const TITLE : &'static str = "Alchemical Reduction";
+
+    type Input = Vec<char>;
+    type Part1 = usize;
+    type Part2 = LessProblematic;
+    type Error = Day05Error;
+
+    fn parse(&self, input: &Input) -> Result<Vec<char>, Day05Error> {
+        return Ok(input.first_line()?.chars().collect());
+    }
+
+    fn part1(&self, polymer_chars: &Vec<char>) -> Result<usize, Day05Error> {
+        let mut polymer = Polymer::new(polymer_chars)?;
+        let mut changed = true;
+        while changed { changed = polymer.run_iteration(); }
+        return Ok(polymer.get_last_iteration().len());
+    }
+
+    fn part2(&self, polymer_chars: &Vec<char>) -> Result<LessProblematic, Day05Error> {
+        let mut problematic_polymer = ProblematicPolymer::new(polymer_chars)?;
+        problematic_polymer.run_polymers();
+        let (removed_type, final_polymer, _) = problematic_polymer.get_less_problematic().ok_or(Day05Error::NoUnitTypes)?;
+        return Ok(LessProblematic { removed_type: removed_type, units: final_polymer.len() });
+    }
+
+    fn examples() -> Vec<Example<Day05>> {
+        return vec![
+            Example::new("dabAcCaCBAcCcaDA", Day05).part1("10").part2("4"),
+            Example::new("aA", Day05).part1("0"),
+            Example::new("abBA", Day05).part1("0"),
+            Example::new("abAB", Day05).part1("4"),
+            Example::new("aabAAB", Day05).part1("6"),
+        ];
+    }
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_example(example: &str) -> Vec<char> {
+        return Day05.parse(&Input::from_string("example", example)).unwrap();
+    }
+
+    #[test]
+    fn fully_reacts_polymer() {
+        assert_eq!(Day05.part1(&parse_example("aA")).unwrap(), 0);
+        assert_eq!(Day05.part1(&parse_example("abBA")).unwrap(), 0);
+        assert_eq!(Day05.part1(&parse_example("abAB")).unwrap(), 4);
+        assert_eq!(Day05.part1(&parse_example("aabAAB")).unwrap(), 6);
+        assert_eq!(Day05.part1(&parse_example("dabAcCaCBAcCcaDA")).unwrap(), 10);
+    }
+
+    #[test]
+    fn removes_most_problematic_unit_type() {
+        let less_problematic : LessProblematic = Day05.part2(&parse_example("dabAcCaCBAcCcaDA")).unwrap();
+        assert_eq!(less_problematic.removed_type, 'C');
+        assert_eq!(less_problematic.units, 4);
+    }
+
+    #[test]
+    fn rejects_units_without_polarity() {
+        assert!(matches!(Day05.part1(&parse_example("aA1")), Err(Day05Error::InvalidUnit { unit: '1', position: 2 })));
+    }
 }
\ No newline at end of file
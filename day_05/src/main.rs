@@ -1,23 +1,32 @@
-mod read;
-mod lib;
+use common::{CountingAllocator, OutputFormat, Solution, check_example, example_from_args, init_logging, print_json_answers, print_solution_stats, stats_from_args, verbosity_from_args};
+use read::Input;
+use day_05::Day05;
 
-use lib::{Polymer, ProblematicPolymer};
+#[global_allocator]
+static ALLOCATOR : CountingAllocator = CountingAllocator;
 
 fn main() {
 
-    let polymers_chars = read::read_chars("input.txt".to_owned());
-    let polymer_chars = polymers_chars.get(0).unwrap();
+    init_logging(verbosity_from_args());
+    if let Some(example) = example_from_args().unwrap() {
+        std::process::exit(if check_example::<Day05>(example).unwrap() { 0 } else { 1 });
+    }
+
+    let input = Input::from_path("input.txt").unwrap();
+    if OutputFormat::from_args().unwrap() == OutputFormat::Json {
+        return print_json_answers(&Day05, &input).unwrap();
+    }
+    if stats_from_args() {
+        return print_solution_stats(&Day05, &input).unwrap();
+    }
+
+    let polymer_chars = Day05.parse(&input).unwrap();
     
     // Part 1
-    let mut polymer = Polymer::new(polymer_chars);
-    let mut changed = true;
-    while changed { changed = polymer.run_iteration(); }
-    let last_polymer = polymer.get_last_iteration();
-    println!("\r🧫 Last polymer has '{}' units (Part 1)", last_polymer.len());
+    let last_polymer_units = Day05.part1(&polymer_chars).unwrap();
+    println!("\r🧫 Last polymer has '{}' units (Part 1)", last_polymer_units);
     
     // Part 2
-    let mut problematic_polymer = ProblematicPolymer::new(polymer_chars);
-    problematic_polymer.run_polymers();
-    let problematic_result = problematic_polymer.get_less_problematic().unwrap();
-    println!("\r🧫 Less problematic polymer found for '{}' has '{}' units (Part 2)", problematic_result.0, problematic_result.1.len());
+    let problematic_result = Day05.part2(&polymer_chars).unwrap();
+    println!("\r🧫 Less problematic polymer found for '{}' has '{}' units (Part 2)", problematic_result.removed_type, problematic_result.units);
 }
\ No newline at end of file
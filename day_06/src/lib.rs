@@ -1,94 +1,127 @@
 use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use common::{BLACK, BoundingBox, Color, DayNumber, Example, Grid, Image, Point, Solution, palette_color, shade_color};
+use read::Input;
+
+// ======================================================= CONSTANTS DEFINITIONS =======================================================
+
+pub const PART_2_THRESHOLD : CoordinateUnit = 10000;
+
+/// Bounds keeping the mapping (every point of the area, measured from every coordinate) within reach of the puzzle size.
+const MAX_COORDINATE : CoordinateUnit = 1_000_000_000;
+const MAX_MAP_AREA : CoordinateUnit = 4_000_000;
+const MAX_DISTANCE_COMPUTATIONS : CoordinateUnit = 200_000_000;
+
+const TIE_COLOR : Color = [128, 128, 128];
+const RENDER_SCALE : usize = 2;
 
 // ======================================================== STRUCTS DEFINITIONS ========================================================
 
 pub type CoordinateUnit = i64;
 
-#[derive(Eq, PartialEq, Hash, Clone, Copy)]
-pub struct CoordinatePoint {
-    x: CoordinateUnit,
-    y: CoordinateUnit
-}
+pub type CoordinatePoint = Point<CoordinateUnit>;
 
+/// Coordinates over their area, mapping every point to its single closest coordinate (`None` on ties) and its sum of distances.
 pub struct Map {
     points: Vec<CoordinatePoint>,
-    top_left: CoordinatePoint,
-    bottom_right: CoordinatePoint,
-    closest_mapping: HashMap<CoordinatePoint, HashSet<usize>>,
-    full_mapping: HashMap<CoordinatePoint, HashMap<usize, CoordinateUnit>>
+    limits: BoundingBox<CoordinateUnit>,
+    closest_mapping: Grid<Option<usize>>,
+    distance_sums: Grid<CoordinateUnit>
+}
+
+#[derive(Serialize)]
+pub struct LargestArea {
+    pub section: usize,
+    pub area: usize,
+}
+
+#[derive(Debug)]
+pub enum Day06Error {
+    InvalidCoordinate(String),
+    NoCoordinates,
+    MapTooLarge,
+    OnlyInfiniteAreas,
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Day06 {
+    pub threshold: CoordinateUnit,
 }
 
 // ======================================================= AUXILIARY FUNCTIONS =======================================================
 
-fn manhantan_distance(point_1: &CoordinatePoint, point_2: &CoordinatePoint) -> CoordinateUnit {
-    let x_difference : CoordinateUnit = point_1.get_x() - point_2.get_x();
-    let y_difference : CoordinateUnit = point_1.get_y() - point_2.get_y();
-    return x_difference.abs() + y_difference.abs();
+pub fn parse_coordinate(coordinate_string: &str) -> Result<CoordinatePoint, Day06Error> {
+    let invalid_coordinate = || Day06Error::InvalidCoordinate(coordinate_string.to_owned());
+    let (coordinate_x_string, coordinate_y_string) = coordinate_string.split_once(", ").ok_or_else(invalid_coordinate)?;
+    let coordinate_x : CoordinateUnit = coordinate_x_string.trim().parse().map_err(|_| invalid_coordinate())?;
+    let coordinate_y : CoordinateUnit = coordinate_y_string.trim().parse().map_err(|_| invalid_coordinate())?;
+    if coordinate_x.unsigned_abs() > MAX_COORDINATE as u64 || coordinate_y.unsigned_abs() > MAX_COORDINATE as u64 { return Err(invalid_coordinate()); }
+    return Ok(CoordinatePoint::new(coordinate_x, coordinate_y));
 }
 
 // ======================================================== STRUCTS IMPLEMENTATIONS ========================================================
 
-impl CoordinatePoint {
-    pub fn new(point_x: CoordinateUnit, point_y: CoordinateUnit) -> CoordinatePoint {
-        CoordinatePoint { x: point_x, y: point_y }
+impl fmt::Display for LargestArea {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}", self.area)
     }
+}
 
-    pub fn get_x(&self) -> CoordinateUnit { self.x }
-    pub fn get_y(&self) -> CoordinateUnit { self.y }
+impl fmt::Display for Day06Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day06Error::InvalidCoordinate(coordinate) => write!(formatter, "coordinate '{}' could not be parsed", coordinate),
+            Day06Error::NoCoordinates => write!(formatter, "no coordinates to build the map from"),
+            Day06Error::MapTooLarge => write!(formatter, "coordinates are spread over more than {} points", MAX_MAP_AREA),
+            Day06Error::OnlyInfiniteAreas => write!(formatter, "every section has an infinite area"),
+        }
+    }
 }
 
+impl Error for Day06Error {}
+
 impl Map {
-    pub fn new(points: Vec<CoordinatePoint>) -> Map {
-
-        let mut top_left_x : Option<CoordinateUnit> = None;
-        let mut top_left_y : Option<CoordinateUnit> = None;
-        let mut bottom_right_x : Option<CoordinateUnit> = None;
-        let mut bottom_right_y : Option<CoordinateUnit> = None;
-
-        for point in points.iter() {
-            if top_left_x.is_none() || top_left_x.unwrap() > point.get_x() { top_left_x = Some(point.get_x()) }
-            if top_left_y.is_none() || top_left_y.unwrap() > point.get_y() { top_left_y = Some(point.get_y()) }
-            if bottom_right_x.is_none() || bottom_right_x.unwrap() < point.get_x() { bottom_right_x = Some(point.get_x()) }
-            if bottom_right_y.is_none() || bottom_right_y.unwrap() < point.get_y() { bottom_right_y = Some(point.get_y()) }
-        }
+    pub fn new(points: Vec<CoordinatePoint>) -> Result<Map, Day06Error> {
+        let limits : BoundingBox<CoordinateUnit> = BoundingBox::from_points(&points).ok_or(Day06Error::NoCoordinates)?.expand(5);
+        let area : CoordinateUnit = limits.width().saturating_mul(limits.height());
+        if area > MAX_MAP_AREA || area.saturating_mul(points.len() as CoordinateUnit) > MAX_DISTANCE_COMPUTATIONS { return Err(Day06Error::MapTooLarge); }
 
-        Map {
+        Ok(Map {
             points: points,
-            top_left: CoordinatePoint::new(top_left_x.unwrap() - 5, top_left_y.unwrap() - 5),
-            bottom_right: CoordinatePoint::new(bottom_right_x.unwrap() + 5, bottom_right_y.unwrap() + 5),
-            closest_mapping: HashMap::new(),
-            full_mapping: HashMap::new()
-        }
+            limits: limits,
+            closest_mapping: Grid::new(limits, None),
+            distance_sums: Grid::new(limits, 0)
+        })
     }
 
     pub fn compute_mapping(&mut self) {
 
-        for verify_point_x in self.top_left.get_x()..=self.bottom_right.get_x() {
-            for verify_point_y in self.top_left.get_y()..=self.bottom_right.get_y() {
+        for verify_point in self.limits.points() {
 
-                let verify_point : CoordinatePoint = CoordinatePoint::new(verify_point_x, verify_point_y);
-
-                let mut current_min_distance : Option<CoordinateUnit> = None;
-                let mut current_points : HashSet<usize> = HashSet::new();
-                let mut current_distances : HashMap<usize, CoordinateUnit> = HashMap::new();
+            let mut current_min_distance : Option<CoordinateUnit> = None;
+            let mut current_closest : Option<usize> = None;
+            let mut current_sum_distances : CoordinateUnit = 0;
+            
+            for (index_point, coordinate_point) in self.points.iter().enumerate() {
                 
-                for (index_point, coordinate_point) in self.points.iter().enumerate() {
-                    
-                    let distance = manhantan_distance(coordinate_point, &verify_point);
-                    current_distances.insert(index_point, distance);
-
-                    if current_min_distance.is_none() || current_min_distance.unwrap() > distance {
-                        current_min_distance = Some(distance);
-                        current_points = HashSet::new();
-                        current_points.insert(index_point);
-                    } else if current_min_distance.is_some() && current_min_distance.unwrap() == distance {
-                        current_points.insert(index_point);
-                    } 
-                }
-
-                self.closest_mapping.insert(verify_point, current_points);
-                self.full_mapping.insert(verify_point, current_distances);
+                let distance = coordinate_point.manhattan_distance(&verify_point);
+                current_sum_distances = current_sum_distances + distance;
+
+                if current_min_distance.is_none() || current_min_distance.unwrap() > distance {
+                    current_min_distance = Some(distance);
+                    current_closest = Some(index_point);
+                } else if current_min_distance.is_some() && current_min_distance.unwrap() == distance {
+                    current_closest = None;
+                } 
             }
+
+            self.closest_mapping[verify_point] = current_closest;
+            self.distance_sums[verify_point] = current_sum_distances;
         }
 
     }
@@ -96,24 +129,9 @@ impl Map {
     fn get_limitted_indexes(&self) -> HashSet<usize> {
 
         let mut current_indexes : HashSet<usize> = self.points.iter().enumerate().map(|(index, _)| index).collect();
-        for check_point_x in self.top_left.get_x()..=self.bottom_right.get_x() {
-            for check_point_y in vec![self.top_left.get_y(), self.bottom_right.get_y()] {
-                let check_point : CoordinatePoint = CoordinatePoint::new(check_point_x, check_point_y);
-                let associated_indexes = self.closest_mapping.get(&check_point).unwrap();
-                if associated_indexes.len() == 1 {
-                    let associated_index = associated_indexes.iter().next().unwrap();
-                    current_indexes.remove(associated_index);
-                }
-            }
-        }
-        for check_point_x in vec![self.top_left.get_x(), self.bottom_right.get_x()] {
-            for check_point_y in self.top_left.get_y()..=self.bottom_right.get_y() {
-                let check_point : CoordinatePoint = CoordinatePoint::new(check_point_x, check_point_y);
-                let associated_indexes = self.closest_mapping.get(&check_point).unwrap();
-                if associated_indexes.len() == 1 {
-                    let associated_index = associated_indexes.iter().next().unwrap();
-                    current_indexes.remove(associated_index);
-                }
+        for check_point in self.limits.points().filter(|point| self.limits.is_on_border(point)) {
+            if let Some(associated_index) = self.closest_mapping[check_point] {
+                current_indexes.remove(&associated_index);
             }
         }
 
@@ -126,19 +144,12 @@ impl Map {
         let mut mapping_areas : HashMap<usize, usize> = limitted_indexes.iter()
             .map(|&index| (index, 0)).collect();
 
-        for check_point_x in self.top_left.get_x()..=self.bottom_right.get_x() {
-            for check_point_y in self.top_left.get_y()..=self.bottom_right.get_y() {
-
-                let check_point : CoordinatePoint = CoordinatePoint::new(check_point_x, check_point_y);
-                let associated_indexes = self.closest_mapping.get(&check_point).unwrap();
-                if associated_indexes.len() == 1 {
-                    let associated_index = associated_indexes.iter().next().unwrap();
-                    if limitted_indexes.contains(associated_index) {
-                        let current_area = mapping_areas.get_mut(associated_index).unwrap();
-                        *current_area = *current_area + 1;
-                    }
+        for check_point in self.limits.points() {
+            if let Some(associated_index) = self.closest_mapping[check_point] {
+                if limitted_indexes.contains(&associated_index) {
+                    let current_area = mapping_areas.get_mut(&associated_index).unwrap();
+                    *current_area = *current_area + 1;
                 }
-
             }
         }
         
@@ -149,17 +160,118 @@ impl Map {
 
         let mut valid_points : HashSet<CoordinatePoint> = HashSet::new();
 
-        for check_point_x in self.top_left.get_x()..=self.bottom_right.get_x() {
-            for check_point_y in self.top_left.get_y()..=self.bottom_right.get_y() {
-
-                let check_point : CoordinatePoint = CoordinatePoint::new(check_point_x, check_point_y);
-                let sum_distances : CoordinateUnit = self.full_mapping.get(&check_point).unwrap()
-                    .iter().map(|(_, &distance)| distance).sum();
-                if sum_distances < threshold { valid_points.insert(check_point); }
-
-            }
+        for (check_point, &sum_distances) in self.distance_sums.iter() {
+            if sum_distances < threshold { valid_points.insert(check_point); }
         }
         
         return valid_points;
     }
+
+    /// Voronoi regions of the coordinates (infinite ones dimmed, ties in gray), lightened where the sum of distances is under `threshold`.
+    pub fn render_regions(&self, threshold: CoordinateUnit) -> Image {
+        let limitted_indexes : HashSet<usize> = self.get_limitted_indexes();
+        let mut image : Image = Image::from_grid(&self.closest_mapping, |point, &associated_index| {
+            let color : Color = match associated_index {
+                Some(index) if limitted_indexes.contains(&index) => palette_color(index),
+                Some(index) => shade_color(palette_color(index), 0.45),
+                None => TIE_COLOR,
+            };
+            if self.distance_sums[point] < threshold { color.map(|channel| channel / 2 + 128) } else { color }
+        });
+
+        for point in self.points.iter() {
+            let offset : CoordinatePoint = *point - self.limits.min;
+            image.set_pixel(offset.x as usize, offset.y as usize, BLACK);
+        }
+
+        return image.scale(RENDER_SCALE);
+    }
+}
+
+// ============================================================= EXAMPLES ==============================================================
+
+const EXAMPLE : &str = "1, 1\n1, 6\n8, 3\n3, 4\n5, 5\n8, 9";
+
+// ====================================================== SOLUTION IMPLEMENTATION ======================================================
+
+impl Default for Day06 {
+    fn default() -> Day06 { Day06 { threshold: PART_2_THRESHOLD } }
+}
+
+impl Solution for Day06 {
+    const DAY : DayNumber = 6;
+    const TITLE : &'static str = "Chronal Coordinates";
+
+    type Input = Map;
+    type Part1 = LargestArea;
+    type Part2 = usize;
+    type Error = Day06Error;
+
+    fn parse(&self, input: &Input) -> Result<Map, Day06Error> {
+        let coordinates : Vec<CoordinatePoint> = input.lines().iter()
+            .map(|coordinate_string| parse_coordinate(coordinate_string))
+            .collect::<Result<_, _>>()?;
+        let mut map : Map = Map::new(coordinates)?;
+        map.compute_mapping();
+
+        return Ok(map);
+    }
+
+    fn part1(&self, map: &Map) -> Result<LargestArea, Day06Error> {
+        let (section, area) = map.get_area_for_limitted().into_iter()
+            .max_by_key(|(_, area)| *area)
+            .ok_or(Day06Error::OnlyInfiniteAreas)?;
+        return Ok(LargestArea { section: section, area: area });
+    }
+
+    fn part2(&self, map: &Map) -> Result<usize, Day06Error> {
+        return Ok(map.get_points_with_sum_less(self.threshold).len());
+    }
+
+    fn render(&self, map: &Map) -> Result<Vec<Image>, Day06Error> {
+        return Ok(vec![map.render_regions(self.threshold)]);
+    }
+
+    fn examples() -> Vec<Example<Day06>> {
+        return vec![
+            Example::new(EXAMPLE, Day06 { threshold: 32 }).part1("17").part2("16"),
+        ];
+    }
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_largest_finite_area() {
+        let map : Map = Day06::default().parse(&Input::from_string("example", EXAMPLE)).unwrap();
+        assert_eq!(Day06::default().part1(&map).unwrap().area, 17);
+    }
+
+    #[test]
+    fn measures_region_close_to_all_coordinates() {
+        let solution : Day06 = Day06 { threshold: 32 };
+        let map : Map = solution.parse(&Input::from_string("example", EXAMPLE)).unwrap();
+        assert_eq!(solution.part2(&map).unwrap(), 16);
+    }
+
+    #[test]
+    fn rejects_malformed_coordinates() {
+        assert!(matches!(parse_coordinate("1; 1"), Err(Day06Error::InvalidCoordinate(_))));
+        assert!(matches!(parse_coordinate("1, -9223372036854775808"), Err(Day06Error::InvalidCoordinate(_))));
+        assert!(matches!(Map::new(Vec::new()), Err(Day06Error::NoCoordinates)));
+        assert!(matches!(Map::new(vec![CoordinatePoint::new(0, 0), CoordinatePoint::new(5_000, 5_000)]), Err(Day06Error::MapTooLarge)));
+    }
+
+    #[test]
+    fn renders_voronoi_regions() {
+        let map : Map = Day06::default().parse(&Input::from_string("example", EXAMPLE)).unwrap();
+        let image : Image = map.render_regions(0);
+        assert_eq!((image.get_width(), image.get_height()), (36, 38));
+        assert_eq!(image.get_pixel(10, 10), Some(BLACK));
+        assert_eq!(image.get_pixel(0, 0), Some(shade_color(palette_color(0), 0.45)));
+    }
 }
\ No newline at end of file
@@ -1,29 +1,38 @@
-mod read;
-mod lib;
+use common::{CountingAllocator, OutputFormat, Solution, check_example, example_from_args, init_logging, print_json_answers, print_solution_stats, render_path_from_args, save_render, solution_from_args, stats_from_args, verbosity_from_args};
+use read::Input;
+use day_06::Day06;
 
-use lib::{CoordinateUnit, CoordinatePoint, Map};
-
-const PART_2_THRESHOLD : CoordinateUnit = 10000; 
+#[global_allocator]
+static ALLOCATOR : CountingAllocator = CountingAllocator;
 
 fn main() {
 
-    let coordinates_string = read::read_lines("input.txt".to_owned());
-    let coordinates : Vec<CoordinatePoint> = coordinates_string.into_iter()
-        .map(|coordinate_string| {
-            let mut coordinate_split = coordinate_string.split(", ");
-            let coordinate_x : CoordinateUnit = coordinate_split.next().unwrap().parse().unwrap();
-            let coordinate_y : CoordinateUnit = coordinate_split.next().unwrap().parse().unwrap();
-            return CoordinatePoint::new(coordinate_x, coordinate_y);
-        }).collect();
-    let mut map : Map = Map::new(coordinates);
-    map.compute_mapping();
+    init_logging(verbosity_from_args());
+    if let Some(example) = example_from_args().unwrap() {
+        std::process::exit(if check_example::<Day06>(example).unwrap() { 0 } else { 1 });
+    }
+
+    let input = Input::from_path("input.txt").unwrap();
+    let solution : Day06 = solution_from_args().unwrap();
+    if let Some(render_path) = render_path_from_args().unwrap() {
+        for frame_path in save_render(&solution, &input, &render_path).unwrap() {
+            eprintln!("🖼️  Rendered '{}'", frame_path.display());
+        }
+    }
+    if OutputFormat::from_args().unwrap() == OutputFormat::Json {
+        return print_json_answers(&solution, &input).unwrap();
+    }
+    if stats_from_args() {
+        return print_solution_stats(&solution, &input).unwrap();
+    }
+
+    let map = solution.parse(&input).unwrap();
     
     // Part 1
-    let mapping = map.get_area_for_limitted();
-    let max = mapping.into_iter().max_by_key(|(_, area)| *area).unwrap();
-    println!("\r🛰️  Max area of '{}' from non-infinite '{}' section (Part 1)", max.1, max.0);
+    let largest_area = solution.part1(&map).unwrap();
+    println!("\r🛰️  Max area of '{}' from non-infinite '{}' section (Part 1)", largest_area.area, largest_area.section);
     
     // Part 2
-    let region_of_interest = map.get_points_with_sum_less(PART_2_THRESHOLD);
-    println!("\r🛰️  The region of interest for threshold '{}' has '{}' points (Part 2)", PART_2_THRESHOLD, region_of_interest.len());
+    let region_of_interest = solution.part2(&map).unwrap();
+    println!("\r🛰️  The region of interest for threshold '{}' has '{}' points (Part 2)", solution.threshold, region_of_interest);
 }
\ No newline at end of file
@@ -0,0 +1,11 @@
+use std::process::{Command, Output};
+
+#[test]
+fn solves_checked_in_input() {
+    let output : Output = Command::new(env!("CARGO_BIN_EXE_day_06")).current_dir(env!("CARGO_MANIFEST_DIR")).output().unwrap();
+    let stdout : String = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.contains("'3620'"), "unexpected part 1 output: {}", stdout);
+    assert!(stdout.contains("'39930' points (Part 2)"), "unexpected part 2 output: {}", stdout);
+}
\ No newline at end of file
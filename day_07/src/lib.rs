@@ -1,16 +1,29 @@
 use regex::Regex;
 use std::collections::{HashSet, HashMap};
+use std::error::Error;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use common::{DayNumber, Example, Solution};
+use read::Input;
+
+// ======================================================= CONSTANTS DEFINITIONS =======================================================
+
+pub const NUMBER_WORKERS : usize = 5;
+pub const BASE_DELAY : TimeStep = 60;
 
 // ======================================================== STRUCTS DEFINITIONS ========================================================
 
 type NodeID = char;
+type Dependences = HashMap<NodeID, HashSet<NodeID>>;
 
 pub struct DependenceSolver {
     nodes: HashSet<NodeID>,
     dependences: HashMap<NodeID, HashSet<NodeID>>
 }
 
-type TimeStep = i64;
+pub type TimeStep = i64;
 
 pub struct DependenceSolverMultiple {
     nodes: HashSet<NodeID>,
@@ -19,17 +32,40 @@ pub struct DependenceSolverMultiple {
     dependences: HashMap<NodeID, HashSet<NodeID>>
 } 
 
+#[derive(Serialize)]
+pub struct MultipleOrder {
+    pub order: String,
+    pub number_workers: usize,
+    pub base_delay: TimeStep,
+    pub timesteps: TimeStep,
+}
+
+#[derive(Debug)]
+pub enum Day07Error {
+    InvalidStep(String),
+    NoWorkers,
+    NoValidChoice { completed: String },
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Day07 {
+    pub number_workers: usize,
+    pub base_delay: TimeStep,
+}
+
 // ======================================================== AUXILIARY FUNCTIONS ========================================================
 
-fn get_nodes_and_dependences(dependents_strings: &Vec<String>) -> (HashSet<NodeID>, HashMap<NodeID, HashSet<NodeID>>) {
+fn get_nodes_and_dependences(dependents_strings: &Vec<String>) -> Result<(HashSet<NodeID>, Dependences), Day07Error> {
 
     let dependency_regex : Regex = Regex::new(r"Step ([A-Z]) must be finished before step ([A-Z]) can begin\.").unwrap();
     let mut nodes : HashSet<NodeID> = HashSet::new();
     let mut dependences : HashMap<NodeID, HashSet<NodeID>> = HashMap::new();
 
-    for dependent_string in dependents_strings.into_iter() {
+    for dependent_string in dependents_strings.iter() {
 
-        let capture_groups_regex = dependency_regex.captures(dependent_string).unwrap();
+        let capture_groups_regex = dependency_regex.captures(dependent_string)
+            .ok_or_else(|| Day07Error::InvalidStep(dependent_string.clone()))?;
         let requirement_id : NodeID = capture_groups_regex.get(1).unwrap().as_str()
             .chars().nth(0).unwrap();
         let for_id : NodeID = capture_groups_regex.get(2).unwrap().as_str()
@@ -44,7 +80,7 @@ fn get_nodes_and_dependences(dependents_strings: &Vec<String>) -> (HashSet<NodeI
         dependencies_for.insert(requirement_id);
     }
 
-    return (nodes, dependences);
+    return Ok((nodes, dependences));
 }
 
 fn convert_to_ascii(node: &NodeID) -> u64 {
@@ -60,14 +96,32 @@ fn get_node_delay(node: &NodeID) -> TimeStep {
 
 // ====================================================== STRUCTS IMPLEMENTATIONS ======================================================
 
+impl fmt::Display for MultipleOrder {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}", self.timesteps)
+    }
+}
+
+impl fmt::Display for Day07Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day07Error::InvalidStep(step) => write!(formatter, "step '{}' could not be parsed", step),
+            Day07Error::NoWorkers => write!(formatter, "at least one worker is needed"),
+            Day07Error::NoValidChoice { completed } => write!(formatter, "no possible valid choice for resolution after '{}'", completed),
+        }
+    }
+}
+
+impl Error for Day07Error {}
+
 impl DependenceSolver {
-    pub fn new(dependents_strings: &Vec<String>) -> DependenceSolver {
+    pub fn new(dependents_strings: &Vec<String>) -> Result<DependenceSolver, Day07Error> {
 
-        let (nodes, dependences) = get_nodes_and_dependences(dependents_strings);
-        DependenceSolver { nodes: nodes, dependences: dependences }
+        let (nodes, dependences) = get_nodes_and_dependences(dependents_strings)?;
+        Ok(DependenceSolver { nodes: nodes, dependences: dependences })
     }
 
-    pub fn solve_best_order(&self) -> Vec<NodeID> {
+    pub fn solve_best_order(&self) -> Result<Vec<NodeID>, Day07Error> {
 
         let mut correct_sequence : Vec<NodeID> = Vec::new();
         while self.nodes.len() > correct_sequence.len() {
@@ -84,23 +138,24 @@ impl DependenceSolver {
                 .collect();
 
             // Get choice
-            if valid_choices.len() == 0 { panic!("🚨 No possible valid choice for resolution!") }
-            let choice = valid_choices.iter().min().unwrap();
+            let choice = valid_choices.iter().min()
+                .ok_or_else(|| Day07Error::NoValidChoice { completed: correct_sequence.iter().collect() })?;
             correct_sequence.push(*choice);
         }
 
-        return correct_sequence;
+        return Ok(correct_sequence);
     }
 }
 
 impl DependenceSolverMultiple {
-    pub fn new(dependents_strings: &Vec<String>, number_workers: usize, time_delay: TimeStep) -> DependenceSolverMultiple {
+    pub fn new(dependents_strings: &Vec<String>, number_workers: usize, time_delay: TimeStep) -> Result<DependenceSolverMultiple, Day07Error> {
 
-        let (nodes, dependences) = get_nodes_and_dependences(dependents_strings);
-        DependenceSolverMultiple { nodes: nodes, number_workers: number_workers, time_delay: time_delay, dependences: dependences }
+        if number_workers == 0 { return Err(Day07Error::NoWorkers); }
+        let (nodes, dependences) = get_nodes_and_dependences(dependents_strings)?;
+        Ok(DependenceSolverMultiple { nodes: nodes, number_workers: number_workers, time_delay: time_delay, dependences: dependences })
     }
 
-    pub fn solve_best_order(&self) -> (Vec<NodeID>, TimeStep) {
+    pub fn solve_best_order(&self) -> Result<(Vec<NodeID>, TimeStep), Day07Error> {
 
         let mut current_timestep : TimeStep = 0;
         let mut correct_sequence : Vec<NodeID> = Vec::new();
@@ -136,6 +191,11 @@ impl DependenceSolverMultiple {
                 .map(|(node, _)| *node)
                 .collect();
 
+            // Nothing is being processed and nothing can be started
+            if valid_choices.len() == 0 && available_workers.len() == self.number_workers && self.nodes.len() > correct_sequence.len() {
+                return Err(Day07Error::NoValidChoice { completed: correct_sequence.iter().collect() });
+            }
+
             // Match available choices with available workers
             available_workers.sort();
             valid_choices.sort();
@@ -150,6 +210,93 @@ impl DependenceSolverMultiple {
             current_timestep = current_timestep + 1;
         }
 
-        return (correct_sequence, current_timestep - 1);
+        return Ok((correct_sequence, current_timestep - 1));
+    }
+}
+
+// ============================================================= EXAMPLES ==============================================================
+
+const EXAMPLE : &str = "\
+Step C must be finished before step A can begin.
+Step C must be finished before step F can begin.
+Step A must be finished before step B can begin.
+Step A must be finished before step D can begin.
+Step B must be finished before step E can begin.
+Step D must be finished before step E can begin.
+Step F must be finished before step E can begin.";
+
+// ====================================================== SOLUTION IMPLEMENTATION ======================================================
+
+impl Default for Day07 {
+    fn default() -> Day07 { Day07 { number_workers: NUMBER_WORKERS, base_delay: BASE_DELAY } }
+}
+
+impl Solution for Day07 {
+    const DAY : DayNumber = 7;
+    const TITLE : &'static str = "The Sum of Its Parts";
+
+    type Input = Vec<String>;
+    type Part1 = String;
+    type Part2 = MultipleOrder;
+    type Error = Day07Error;
+
+    fn parse(&self, input: &Input) -> Result<Vec<String>, Day07Error> {
+        let dependents_strings : Vec<String> = input.lines();
+        get_nodes_and_dependences(&dependents_strings)?;
+        return Ok(dependents_strings);
+    }
+
+    fn part1(&self, dependents_strings: &Vec<String>) -> Result<String, Day07Error> {
+        let solver = DependenceSolver::new(dependents_strings)?;
+        return Ok(solver.solve_best_order()?.iter().collect());
+    }
+
+    fn part2(&self, dependents_strings: &Vec<String>) -> Result<MultipleOrder, Day07Error> {
+        let multi_solver = DependenceSolverMultiple::new(dependents_strings, self.number_workers, self.base_delay)?;
+        let (order, timesteps) = multi_solver.solve_best_order()?;
+        return Ok(MultipleOrder {
+            order: order.iter().collect(),
+            number_workers: self.number_workers,
+            base_delay: self.base_delay,
+            timesteps: timesteps,
+        });
+    }
+
+    fn examples() -> Vec<Example<Day07>> {
+        return vec![
+            Example::new(EXAMPLE, Day07 { number_workers: 2, base_delay: 0 }).part1("CABDFE").part2("15"),
+        ];
+    }
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orders_steps_alone() {
+        let solution : Day07 = Day07::default();
+        let dependents_strings : Vec<String> = solution.parse(&Input::from_string("example", EXAMPLE)).unwrap();
+        assert_eq!(solution.part1(&dependents_strings).unwrap(), "CABDFE");
+    }
+
+    #[test]
+    fn orders_steps_with_helpers() {
+        let solution : Day07 = Day07 { number_workers: 2, base_delay: 0 };
+        let dependents_strings : Vec<String> = solution.parse(&Input::from_string("example", EXAMPLE)).unwrap();
+        let multiple_order : MultipleOrder = solution.part2(&dependents_strings).unwrap();
+        assert_eq!(multiple_order.order, "CABFDE");
+        assert_eq!(multiple_order.timesteps, 15);
+    }
+
+    #[test]
+    fn rejects_cyclic_dependences() {
+        let cyclic : Vec<String> = vec![
+            "Step A must be finished before step B can begin.".to_owned(),
+            "Step B must be finished before step A can begin.".to_owned()];
+        assert!(matches!(Day07::default().part1(&cyclic), Err(Day07Error::NoValidChoice { .. })));
+        assert!(matches!(Day07::default().part2(&cyclic), Err(Day07Error::NoValidChoice { .. })));
     }
 }
\ No newline at end of file
@@ -1,21 +1,34 @@
-mod read;
-mod lib;
+use common::{CountingAllocator, OutputFormat, Solution, check_example, example_from_args, init_logging, print_json_answers, print_solution_stats, solution_from_args, stats_from_args, verbosity_from_args};
+use read::Input;
+use day_07::Day07;
+
+#[global_allocator]
+static ALLOCATOR : CountingAllocator = CountingAllocator;
 
-use lib::{DependenceSolver, DependenceSolverMultiple};
 fn main() {
 
-    let dependents_strings = read::read_lines("input.txt".to_owned());
+    init_logging(verbosity_from_args());
+    if let Some(example) = example_from_args().unwrap() {
+        std::process::exit(if check_example::<Day07>(example).unwrap() { 0 } else { 1 });
+    }
+
+    let input = Input::from_path("input.txt").unwrap();
+    let solution : Day07 = solution_from_args().unwrap();
+    if OutputFormat::from_args().unwrap() == OutputFormat::Json {
+        return print_json_answers(&solution, &input).unwrap();
+    }
+    if stats_from_args() {
+        return print_solution_stats(&solution, &input).unwrap();
+    }
+
+    let dependents_strings = solution.parse(&input).unwrap();
     
     // Part 1
-    let solver = DependenceSolver::new(&dependents_strings);
-    let correct_sequence = solver.solve_best_order();
-    println!("\r🛠️  Best task order possible: '{}' (Part 1)", correct_sequence.iter().collect::<String>());
+    let correct_sequence = solution.part1(&dependents_strings).unwrap();
+    println!("\r🛠️  Best task order possible: '{}' (Part 1)", correct_sequence);
     
     // Part 2
-    let number_of_workers : usize = 5;
-    let base_delay : i64 = 60;
-    let multi_solver = DependenceSolverMultiple::new(&dependents_strings, number_of_workers, base_delay);
-    let multi_solution = multi_solver.solve_best_order();
+    let multi_solution = solution.part2(&dependents_strings).unwrap();
     println!("\r🛠️  Best task order possible ('{}' workers, '{}' base delay): '{}' in '{}' timesteps (Part 2)",
-        number_of_workers, base_delay, multi_solution.0.iter().collect::<String>(), multi_solution.1);
+        multi_solution.number_workers, multi_solution.base_delay, multi_solution.order, multi_solution.timesteps);
 }
\ No newline at end of file
@@ -2,8 +2,13 @@
 // ======================================================== STRUCTS DEFINITIONS ========================================================
 
 use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
 
-type Code = i64;
+use common::{DayNumber, Example, Solution};
+use read::{Input, ReadError};
+
+pub type Code = i64;
 type ID = Code;
 
 #[derive(Debug)]
@@ -12,82 +17,219 @@ struct Node {
     metadatas: Vec<Code>
 }
 
+/// Node being decoded, waiting for its remaining children before its metadata can be read.
+struct PendingNode {
+    id: ID,
+    children: Vec<ID>,
+    remaining_children: usize,
+    number_of_metadatas: usize,
+}
+
 pub struct SystemDecoder {
     codes: Vec<Code>,
     nodes: HashMap<ID, Node>,
 }
 
+#[derive(Debug)]
+pub enum Day08Error {
+    Read(ReadError),
+    TruncatedTree { position: usize },
+    NegativeHeader { position: usize, value: Code },
+    TrailingCodes { position: usize },
+    EmptyTree,
+    ValueOverflow,
+}
+
 // ====================================================== STRUCTS IMPLEMENTATIONS ======================================================
 
-impl Node {
-    fn get_node_value(&self, nodes: &HashMap<ID, Node>) -> i64 {
-        if self.children.len() == 0 { self.metadatas.iter().sum() }
-        else { self.metadatas.iter()
-            .filter(|&meta_index| *meta_index != 0 && *meta_index <= self.children.len() as ID)
-            .map(|&meta_index| self.children.get(meta_index as usize - 1).unwrap())
-            .map(|children_id| nodes.get(children_id).unwrap())
-            .map(|children| children.get_node_value(nodes))
-            .sum()
+impl fmt::Display for Day08Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day08Error::Read(error) => write!(formatter, "{}", error),
+            Day08Error::TruncatedTree { position } => write!(formatter, "tree ends abruptly, code expected at position {}", position),
+            Day08Error::NegativeHeader { position, value } => write!(formatter, "header quantity '{}' at position {} is negative", value, position),
+            Day08Error::TrailingCodes { position } => write!(formatter, "codes remain after the root node, from position {}", position),
+            Day08Error::EmptyTree => write!(formatter, "tree has no root node"),
+            Day08Error::ValueOverflow => write!(formatter, "value of the tree does not fit in 64 bits"),
+        }
+    }
+}
+
+impl Error for Day08Error {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Day08Error::Read(error) => Some(error),
+            _ => None,
         }
     }
 }
 
+impl From<ReadError> for Day08Error {
+    fn from(error: ReadError) -> Day08Error { Day08Error::Read(error) }
+}
+
 impl SystemDecoder {
     pub fn new(codes: Vec<Code>) -> SystemDecoder {
         SystemDecoder { codes: codes, nodes: HashMap::new() }
     }
 
-    pub fn decode_codes(&mut self) {
+    /// Nodes are decoded from an explicit stack instead of recursively, so that deep trees cannot overflow the call stack.
+    pub fn decode_codes(&mut self) -> Result<(), Day08Error> {
+        if self.codes.is_empty() { return Ok(()); }
 
         let mut current_code_index : usize = 0;
-        let mut current_node_id : ID = 0;
+        let mut last_node_id : ID = 0;
         let mut nodes : HashMap<ID, Node> = HashMap::new();
-        while current_code_index < self.codes.len() {
-            (current_code_index, current_node_id, nodes) = self.create_node(current_code_index, current_node_id, nodes);
+        let mut pending_nodes : Vec<PendingNode> = vec![self.read_header(&mut current_code_index, last_node_id)?];
+
+        while let Some(pending_node) = pending_nodes.last_mut() {
+
+            // Decode the next child first, if any is left
+            if pending_node.remaining_children > 0 {
+                pending_node.remaining_children = pending_node.remaining_children - 1;
+                last_node_id = last_node_id + 1;
+                pending_node.children.push(last_node_id);
+                let child_node : PendingNode = self.read_header(&mut current_code_index, last_node_id)?;
+                pending_nodes.push(child_node);
+                continue;
+            }
+
+            // Complete the node with its metadata
+            let pending_node : PendingNode = pending_nodes.pop().unwrap();
+            let metadatas_end : usize = current_code_index.saturating_add(pending_node.number_of_metadatas);
+            let metadatas : &[Code] = self.codes.get(current_code_index..metadatas_end)
+                .ok_or(Day08Error::TruncatedTree { position: self.codes.len() })?;
+            current_code_index = metadatas_end;
+            nodes.insert(pending_node.id, Node { children: pending_node.children, metadatas: metadatas.to_vec() });
         }
 
+        if current_code_index < self.codes.len() { return Err(Day08Error::TrailingCodes { position: current_code_index }); }
+
         self.nodes = nodes;
+        return Ok(());
     }
 
-    fn create_node(&self, mut current_codes_index: usize, mut current_node_id: ID, mut nodes: HashMap<ID, Node>) -> (usize, ID, HashMap<ID, Node>) {
+    fn get_code(&self, position: usize) -> Result<Code, Day08Error> {
+        return self.codes.get(position).copied().ok_or(Day08Error::TruncatedTree { position: position });
+    }
 
-        // Get mandatory information
-        let node_id = current_node_id;
-        let number_of_children = *self.codes.get(current_codes_index).unwrap() as usize;
-        let number_of_metadatas = *self.codes.get(current_codes_index + 1).unwrap() as usize;
-        current_codes_index = current_codes_index + 2;
+    fn get_header_quantity(&self, position: usize) -> Result<usize, Day08Error> {
+        let value : Code = self.get_code(position)?;
+        if value < 0 { return Err(Day08Error::NegativeHeader { position: position, value: value }); }
+        return Ok(value as usize);
+    }
 
-        // Create children nodes
-        let mut children_nodes : Vec<ID> = Vec::new();
-        for _ in 0..number_of_children {
-            current_node_id = current_node_id + 1;
-            children_nodes.push(current_node_id);
-            (current_codes_index, current_node_id, nodes) = self.create_node(current_codes_index, current_node_id, nodes);
-        }
-        
-        // Get node metadata
-        let mut metadatas : Vec<Code> = Vec::new();
-        for _ in 0..number_of_metadatas {
-            let current_metadata = *self.codes.get(current_codes_index).unwrap();
-            metadatas.push(current_metadata);
-            current_codes_index = current_codes_index + 1;
-        }
-        
-        // Add newly created node
-        let new_node : Node = Node { children: children_nodes, metadatas: metadatas };
-        nodes.insert(node_id, new_node);
-        return (current_codes_index, current_node_id, nodes);
+    fn read_header(&self, current_code_index: &mut usize, node_id: ID) -> Result<PendingNode, Day08Error> {
+        let number_of_children = self.get_header_quantity(*current_code_index)?;
+        let number_of_metadatas = self.get_header_quantity(*current_code_index + 1)?;
+        *current_code_index = *current_code_index + 2;
+
+        return Ok(PendingNode { id: node_id, children: Vec::new(), remaining_children: number_of_children, number_of_metadatas: number_of_metadatas });
     }
 
-    pub fn sum_metadata(&self) -> Code {
+    pub fn sum_metadata(&self) -> Result<Code, Day08Error> {
         return self.nodes.iter()
-            .map(|(_, node)| &node.metadatas)
-            .flatten()
-            .sum();
+            .flat_map(|(_, node)| &node.metadatas)
+            .try_fold(0, |sum : Code, &metadata| sum.checked_add(metadata))
+            .ok_or(Day08Error::ValueOverflow);
+    }
+
+    /// Children always have greater IDs than their parent, so computing the values from the last node back to the root only ever
+    /// needs values already known.
+    pub fn get_root_value(&self) -> Result<i64, Day08Error> {
+        if !self.nodes.contains_key(&0) { return Err(Day08Error::EmptyTree); }
+
+        let mut node_values : HashMap<ID, i64> = HashMap::new();
+        for node_id in (0..self.nodes.len() as ID).rev() {
+            let node : &Node = &self.nodes[&node_id];
+            let referenced_values : Vec<i64> = match node.children.len() {
+                0 => node.metadatas.clone(),
+                _ => node.metadatas.iter()
+                    .filter(|&meta_index| *meta_index != 0 && *meta_index <= node.children.len() as ID)
+                    .map(|&meta_index| node_values[&node.children[meta_index as usize - 1]])
+                    .collect(),
+            };
+
+            let node_value : i64 = referenced_values.into_iter()
+                .try_fold(0, |sum : i64, value| sum.checked_add(value))
+                .ok_or(Day08Error::ValueOverflow)?;
+            node_values.insert(node_id, node_value);
+        }
+
+        return Ok(node_values[&0]);
     }
+}
+
+// ============================================================= EXAMPLES ==============================================================
+
+const EXAMPLE : &str = "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2";
+
+// ====================================================== SOLUTION IMPLEMENTATION ======================================================
+
+#[derive(Default)]
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY : DayNumber = 8;
+    const TITLE : &'static str = "Memory Maneuver";
+
+    type Input = SystemDecoder;
+    type Part1 = Code;
+    type Part2 = i64;
+    type Error = Day08Error;
+
+    fn parse(&self, input: &Input) -> Result<SystemDecoder, Day08Error> {
+        let codes : Vec<Code> = input.parse_separated_lines::<Code>(" ")?.concat();
+        let mut system_decoder = SystemDecoder::new(codes);
+        system_decoder.decode_codes()?;
+
+        return Ok(system_decoder);
+    }
+
+    fn part1(&self, system_decoder: &SystemDecoder) -> Result<Code, Day08Error> { system_decoder.sum_metadata() }
+    fn part2(&self, system_decoder: &SystemDecoder) -> Result<i64, Day08Error> { system_decoder.get_root_value() }
+
+    fn examples() -> Vec<Example<Day08>> {
+        return vec![
+            Example::new(EXAMPLE, Day08).part1("138").part2("66"),
+        ];
+    }
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sums_all_metadata_entries() {
+        let system_decoder : SystemDecoder = Day08.parse(&Input::from_string("example", EXAMPLE)).unwrap();
+        assert_eq!(Day08.part1(&system_decoder).unwrap(), 138);
+    }
+
+    #[test]
+    fn computes_root_node_value() {
+        let system_decoder : SystemDecoder = Day08.parse(&Input::from_string("example", EXAMPLE)).unwrap();
+        assert_eq!(Day08.part2(&system_decoder).unwrap(), 66);
+    }
+
+    #[test]
+    fn rejects_truncated_tree() {
+        let input : Input = Input::from_string("example", "2 3 0 3 10 11 12");
+        assert!(matches!(Day08.parse(&input), Err(Day08Error::TruncatedTree { position: 7 })));
+        assert!(matches!(Day08.parse(&Input::from_string("example", "0 1 5 0")), Err(Day08Error::TrailingCodes { position: 3 })));
+    }
+
+    #[test]
+    fn decodes_deep_trees_without_recursion() {
+        let depth : usize = 1_000_000;
+        let codes : String = ["1 1"; 1_000_000].join(" ") + " 0 1 7" + &" 1".repeat(depth);
+        let system_decoder : SystemDecoder = Day08.parse(&Input::from_string("deep", &codes)).unwrap();
+        assert_eq!(Day08.part1(&system_decoder).unwrap(), 7 + depth as Code);
+        assert_eq!(Day08.part2(&system_decoder).unwrap(), 7);
 
-    pub fn get_root_value(&self) -> i64 {
-        let root_node = self.nodes.get(&0).unwrap();
-        return root_node.get_node_value(&self.nodes);
+        let system_decoder : SystemDecoder = Day08.parse(&Input::from_string("huge", "0 2 9223372036854775807 1")).unwrap();
+        assert!(matches!(Day08.part1(&system_decoder), Err(Day08Error::ValueOverflow)));
     }
 }
\ No newline at end of file
@@ -1,18 +1,32 @@
-mod read;
-mod lib;
+use common::{CountingAllocator, OutputFormat, Solution, check_example, example_from_args, init_logging, print_json_answers, print_solution_stats, stats_from_args, verbosity_from_args};
+use read::Input;
+use day_08::Day08;
+
+#[global_allocator]
+static ALLOCATOR : CountingAllocator = CountingAllocator;
 
-use lib::SystemDecoder;
 fn main() {
 
-    let system_codes = read::read_list_int_lines("input.txt".to_owned(), " ");
-    let mut system_decoder = SystemDecoder::new(system_codes.get(0).unwrap().clone());
-    system_decoder.decode_codes();
+    init_logging(verbosity_from_args());
+    if let Some(example) = example_from_args().unwrap() {
+        std::process::exit(if check_example::<Day08>(example).unwrap() { 0 } else { 1 });
+    }
+
+    let input = Input::from_path("input.txt").unwrap();
+    if OutputFormat::from_args().unwrap() == OutputFormat::Json {
+        return print_json_answers(&Day08, &input).unwrap();
+    }
+    if stats_from_args() {
+        return print_solution_stats(&Day08, &input).unwrap();
+    }
+
+    let system_decoder = Day08.parse(&input).unwrap();
     
     // Part 1
-    let sum_metadata = system_decoder.sum_metadata();
+    let sum_metadata = Day08.part1(&system_decoder).unwrap();
     println!("\r💻  Sum of nodes metadata: '{}' (Part 1)", sum_metadata);
     
     // Part 2
-    let root_node_value = system_decoder.get_root_value();
+    let root_node_value = Day08.part2(&system_decoder).unwrap();
     println!("\r💻  Root node value: '{}' (Part 2)", root_node_value);
 }
\ No newline at end of file
@@ -1,9 +1,23 @@
 use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use regex::Regex;
+
+use serde::Serialize;
+use tracing::{debug, trace};
+
+use common::{DayNumber, Example, Solution};
+use read::{Input, ReadError};
+
+// ======================================================= CONSTANTS DEFINITIONS =======================================================
+
+const SECOND_PART_MARBLE_FACTOR : usize = 100;
 
 // ======================================================== STRUCTS DEFINITIONS ========================================================
 
 struct AccessibleVector<T> {
-    _first_value: T,
+    first_value: T,
     current_value: T,
     next_mappings: HashMap<T, T>,
     previous_mappings: HashMap<T, T>,
@@ -14,12 +28,38 @@ pub struct MarbleGame {
     max_marble: usize,
 }
 
+#[derive(Serialize)]
+pub struct HighScore {
+    pub player: usize,
+    pub score: i64,
+}
+
+#[derive(Debug)]
+pub enum Day09Error {
+    Read(ReadError),
+    InvalidGameParameters(String),
+    NoPlayers,
+    MarbleLimitOverflow(usize),
+}
+
+// ======================================================== AUXILIARY FUNCTIONS ========================================================
+
+pub fn parse_game_parameters(game_parameters_string: &str) -> Result<(usize, usize), Day09Error> {
+    let invalid_parameters = || Day09Error::InvalidGameParameters(game_parameters_string.to_owned());
+    let parameters_regex : Regex = Regex::new(r"(\d+) players; last marble is worth (\d+) points").unwrap();
+    let capture_groups_regex = parameters_regex.captures(game_parameters_string).ok_or_else(invalid_parameters)?;
+    let number_players : usize = capture_groups_regex.get(1).unwrap().as_str().parse().map_err(|_| invalid_parameters())?;
+    let last_marble_value : usize = capture_groups_regex.get(2).unwrap().as_str().parse().map_err(|_| invalid_parameters())?;
+
+    return Ok((number_players, last_marble_value));
+}
+
 // ====================================================== STRUCTS IMPLEMENTATIONS ======================================================
 
 impl<T : std::fmt::Display + Copy + PartialEq + Eq + std::hash::Hash> AccessibleVector<T> {
     fn new(first_value: T) -> AccessibleVector<T> {
         AccessibleVector {
-            _first_value: first_value,
+            first_value: first_value,
             current_value: first_value,
             next_mappings: vec![(first_value, first_value)].into_iter().collect(),
             previous_mappings: vec![(first_value, first_value)].into_iter().collect()
@@ -65,17 +105,17 @@ impl<T : std::fmt::Display + Copy + PartialEq + Eq + std::hash::Hash> Accessible
         return previous_current_value;
     }
 
-    fn _print_formatted(&self) -> String {
+    fn print_formatted(&self) -> String {
         let mut print_array : Vec<String> = Vec::new();
 
-        let mut iterator_value = self._first_value;
+        let mut iterator_value = self.first_value;
         let mut finished : bool = false;
         while !finished {
             
             if iterator_value != self.current_value { print_array.push(iterator_value.to_string()); }
-            else { print_array.push(format!("({})", iterator_value.to_string())); };
+            else { print_array.push(format!("({})", iterator_value)); };
             iterator_value = *self.next_mappings.get(&iterator_value).unwrap();
-            if iterator_value == self._first_value { finished = true; }
+            if iterator_value == self.first_value { finished = true; }
         }
         
 
@@ -83,9 +123,40 @@ impl<T : std::fmt::Display + Copy + PartialEq + Eq + std::hash::Hash> Accessible
     }
 }
 
+impl fmt::Display for HighScore {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}", self.score)
+    }
+}
+
+impl fmt::Display for Day09Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day09Error::Read(error) => write!(formatter, "{}", error),
+            Day09Error::InvalidGameParameters(parameters) => write!(formatter, "game parameters '{}' could not be parsed", parameters),
+            Day09Error::NoPlayers => write!(formatter, "at least one player is needed to play the game"),
+            Day09Error::MarbleLimitOverflow(max_marble) => write!(formatter, "last marble '{}' is too large to be scaled", max_marble),
+        }
+    }
+}
+
+impl Error for Day09Error {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Day09Error::Read(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ReadError> for Day09Error {
+    fn from(error: ReadError) -> Day09Error { Day09Error::Read(error) }
+}
+
 impl MarbleGame {
-    pub fn new(number_players: usize, max_marble: usize) -> MarbleGame {
-        MarbleGame { number_players: number_players, max_marble: max_marble }
+    pub fn new(number_players: usize, max_marble: usize) -> Result<MarbleGame, Day09Error> {
+        if number_players == 0 { return Err(Day09Error::NoPlayers); }
+        Ok(MarbleGame { number_players: number_players, max_marble: max_marble })
     }
 
     pub fn play_game(&self) -> HashMap<usize, Vec<i64>> {
@@ -111,13 +182,90 @@ impl MarbleGame {
                 let current_player_score = player_scores.get_mut(&current_player).unwrap();
                 current_player_score.push(marble_to_place as i64);
                 current_player_score.push(removed_marble);
+                debug!(player = current_player, marble = marble_to_place, removed_marble = removed_marble, "marble scored");
             }
 
-            //println!("[{}]: {}", current_player, board_game._print_formatted(current_index));
+            trace!("[{}]: {}", current_player, board_game.print_formatted());
             // Update current player
             current_player = current_player + 1;
         }
 
         return player_scores;
     }
+
+    pub fn get_max_score(&self) -> HighScore {
+        let (player, score) = self.play_game().into_iter()
+            .map(|(player, scores)| (player, scores.into_iter().sum()))
+            .max_by_key(|(_, score)| *score)
+            .unwrap();
+        return HighScore { player: player, score: score };
+    }
+}
+
+// ====================================================== SOLUTION IMPLEMENTATION ======================================================
+
+#[derive(Default)]
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY : DayNumber = 9;
+    const TITLE : &'static str = "Marble Mania";
+
+    type Input = (usize, usize);
+    type Part1 = HighScore;
+    type Part2 = HighScore;
+    type Error = Day09Error;
+
+    fn parse(&self, input: &Input) -> Result<(usize, usize), Day09Error> {
+        return parse_game_parameters(input.first_line()?);
+    }
+
+    fn part1(&self, &(number_players, last_marble_value): &(usize, usize)) -> Result<HighScore, Day09Error> {
+        let marble_game : MarbleGame = MarbleGame::new(number_players, last_marble_value)?;
+        return Ok(marble_game.get_max_score());
+    }
+
+    fn part2(&self, &(number_players, last_marble_value): &(usize, usize)) -> Result<HighScore, Day09Error> {
+        let max_marble : usize = last_marble_value.checked_mul(SECOND_PART_MARBLE_FACTOR)
+            .ok_or(Day09Error::MarbleLimitOverflow(last_marble_value))?;
+        let marble_game : MarbleGame = MarbleGame::new(number_players, max_marble)?;
+        return Ok(marble_game.get_max_score());
+    }
+
+    fn examples() -> Vec<Example<Day09>> {
+        return vec![
+            Example::new("9 players; last marble is worth 25 points", Day09).part1("32"),
+            Example::new("10 players; last marble is worth 1618 points", Day09).part1("8317"),
+            Example::new("13 players; last marble is worth 7999 points", Day09).part1("146373"),
+            Example::new("17 players; last marble is worth 1104 points", Day09).part1("2764"),
+            Example::new("21 players; last marble is worth 6111 points", Day09).part1("54718"),
+            Example::new("30 players; last marble is worth 5807 points", Day09).part1("37305"),
+        ];
+    }
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plays_marble_games() {
+        let games : [(usize, usize, i64); 6] = [(9, 25, 32), (10, 1618, 8317), (13, 7999, 146373), (17, 1104, 2764), (21, 6111, 54718), (30, 5807, 37305)];
+        for (number_players, last_marble_value, high_score) in games.into_iter() {
+            assert_eq!(Day09.part1(&(number_players, last_marble_value)).unwrap().score, high_score);
+        }
+    }
+
+    #[test]
+    fn parses_game_parameters() {
+        let input : Input = Input::from_string("example", "10 players; last marble is worth 1618 points");
+        assert_eq!(Day09.parse(&input).unwrap(), (10, 1618));
+    }
+
+    #[test]
+    fn rejects_games_without_players() {
+        assert!(matches!(Day09.part1(&(0, 25)), Err(Day09Error::NoPlayers)));
+    }
 }
\ No newline at end of file
@@ -1,35 +1,32 @@
-use regex::Regex;
+use common::{CountingAllocator, OutputFormat, Solution, check_example, example_from_args, init_logging, print_json_answers, print_solution_stats, stats_from_args, verbosity_from_args};
+use read::Input;
+use day_09::Day09;
 
-mod read;
-mod lib;
-
-use lib::{MarbleGame};
+#[global_allocator]
+static ALLOCATOR : CountingAllocator = CountingAllocator;
 
 fn main() {
 
-    let game_parameters_string = read::read_lines("input.txt".to_owned()).into_iter().nth(0).unwrap();
+    init_logging(verbosity_from_args());
+    if let Some(example) = example_from_args().unwrap() {
+        std::process::exit(if check_example::<Day09>(example).unwrap() { 0 } else { 1 });
+    }
 
-    let parameters_regex : Regex = Regex::new(r"(\d+) players; last marble is worth (\d+) points").unwrap();
-    let capture_groups_regex = parameters_regex.captures(&game_parameters_string).unwrap();
-    let number_players : usize = capture_groups_regex.get(1).unwrap().as_str().parse().unwrap();
-    let last_marble_value : usize = capture_groups_regex.get(2).unwrap().as_str().parse().unwrap();
-    
+    let input = Input::from_path("input.txt").unwrap();
+    if OutputFormat::from_args().unwrap() == OutputFormat::Json {
+        return print_json_answers(&Day09, &input).unwrap();
+    }
+    if stats_from_args() {
+        return print_solution_stats(&Day09, &input).unwrap();
+    }
+
+    let game_parameters = Day09.parse(&input).unwrap();
     
     // Part 1
-    let marble_game : MarbleGame = MarbleGame::new(number_players, last_marble_value);
-    let scores_map = marble_game.play_game();
-    let max_score : (usize, i64) = scores_map.into_iter()
-        .map(|(player, scores)| (player, scores.into_iter().sum()))
-        .max_by_key(|(_, score)| *score)
-        .unwrap();
-    println!("\r🔮  Max score of '{}' achieved by player '{}' (Part 1)", max_score.1, max_score.0);
+    let max_score = Day09.part1(&game_parameters).unwrap();
+    println!("\r🔮  Max score of '{}' achieved by player '{}' (Part 1)", max_score.score, max_score.player);
     
     // Part 2
-    let marble_game : MarbleGame = MarbleGame::new(number_players, last_marble_value * 100);
-    let scores_map = marble_game.play_game();
-    let max_score : (usize, i64) = scores_map.into_iter()
-        .map(|(player, scores)| (player, scores.into_iter().sum()))
-        .max_by_key(|(_, score)| *score)
-        .unwrap();
-    println!("\r🔮  Max score of '{}' achieved by player '{}' (Part 2)", max_score.1, max_score.0);
+    let max_score = Day09.part2(&game_parameters).unwrap();
+    println!("\r🔮  Max score of '{}' achieved by player '{}' (Part 2)", max_score.score, max_score.player);
 }
\ No newline at end of file
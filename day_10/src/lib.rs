@@ -1,17 +1,29 @@
-use std::ops::Add;
 use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::LazyLock;
 
 use queue::Queue;
+use regex::Regex;
+use serde::Deserialize;
+
+use common::{BoundingBox, DayNumber, Example, Point, Solution};
+use read::Input;
+
+// ======================================================= CONSTANTS DEFINITIONS =======================================================
+
+pub const QUEUE_CAPACITY : usize = 10;
+pub const PRINT_SIZE_FACTOR : f64 = 0.25;
+
+static POINT_DEFINITION_REGEX : LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"position=<\s*(-?\d+),\s*(-?\d+)> velocity=<\s*(-?\d+),\s*(-?\d+)>").unwrap()
+});
 
 // ======================================================== STRUCTS DEFINITIONS ========================================================
 
 type CoordinateUnit = i64;
 
-#[derive(Clone, Copy, PartialEq)]
-pub struct Coordinate2D {
-    pub x: CoordinateUnit,
-    pub y: CoordinateUnit,
-}
+pub type Coordinate2D = Point<CoordinateUnit>;
 
 #[derive(Clone, Copy)]
 pub struct PointDefinition {
@@ -27,22 +39,59 @@ pub struct Sky {
     print_size_factor: f64,
 }
 
+#[derive(Debug)]
+pub enum Day10Error {
+    InvalidPointDefinition(String),
+    NoPoints,
+    InvalidQueueCapacity,
+    NoMessage,
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Day10 {
+    pub queue_capacity: usize,
+    pub print_size_factor: f64,
+}
+
 // ====================================================== STRUCTS IMPLEMENTATIONS ======================================================
 
-impl Add for Coordinate2D {
-    type Output = Coordinate2D;
+impl PointDefinition {
+    pub fn from_string(point_definition: &str) -> Result<PointDefinition, Day10Error> {
+        let invalid_definition = || Day10Error::InvalidPointDefinition(point_definition.to_owned());
+        let point_capture = POINT_DEFINITION_REGEX.captures(point_definition).ok_or_else(invalid_definition)?;
 
-    fn add(self, other: Coordinate2D) -> Coordinate2D {
-        Coordinate2D {
-            x: self.x + other.x,
-            y: self.y + other.y,
+        let position_x : i64 = point_capture.get(1).unwrap().as_str().parse().map_err(|_| invalid_definition())?;
+        let position_y : i64 = point_capture.get(2).unwrap().as_str().parse().map_err(|_| invalid_definition())?;
+        let velocity_x : i64 = point_capture.get(3).unwrap().as_str().parse().map_err(|_| invalid_definition())?;
+        let velocity_y : i64 = point_capture.get(4).unwrap().as_str().parse().map_err(|_| invalid_definition())?;
+
+        let position : Coordinate2D = Coordinate2D::new(position_x, position_y);
+        let velocity : Coordinate2D = Coordinate2D::new(velocity_x, velocity_y);
+
+        return Ok(PointDefinition { position: position, velocity: velocity });
+    }
+}
+
+impl fmt::Display for Day10Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day10Error::InvalidPointDefinition(point_definition) => write!(formatter, "point definition '{}' could not be parsed", point_definition),
+            Day10Error::NoPoints => write!(formatter, "there are no points in the sky"),
+            Day10Error::InvalidQueueCapacity => write!(formatter, "densities queue must hold at least one value"),
+            Day10Error::NoMessage => write!(formatter, "points never converged into a message"),
         }
     }
 }
 
+impl Error for Day10Error {}
+
 impl Sky {
-    pub fn new(point_definitions: &Vec<PointDefinition>, queue_capacity: usize, print_size_factor: f64) -> Sky {
-        Sky {
+    pub fn new(point_definitions: &Vec<PointDefinition>, queue_capacity: usize, print_size_factor: f64) -> Result<Sky, Day10Error> {
+        if point_definitions.is_empty() { return Err(Day10Error::NoPoints); }
+        if queue_capacity == 0 { return Err(Day10Error::InvalidQueueCapacity); }
+
+        Ok(Sky {
             iteration: 0,
             point_definitions: point_definitions.iter().enumerate()
                 .map(|(index, point_definition)| (index, point_definition.clone()))    
@@ -52,31 +101,19 @@ impl Sky {
                 .collect(),
             densities: Queue::with_capacity(queue_capacity),
             print_size_factor: print_size_factor,
-        }
+        })
     }
 
     pub fn get_current_iteration(&self) -> usize { self.iteration }
 
-    fn compute_sky_limits(&self) -> Option<(Coordinate2D, Coordinate2D)> {
-        let mut limits : Option<(Coordinate2D, Coordinate2D)> = None;
-        for (_, point) in self.current_sky.iter() {
-            if limits.is_none() { limits = Some((point.clone(), point.clone())) }
-            else {
-                let limits_ref = limits.as_mut().unwrap();
-                if point.x < limits_ref.0.x + 1 { limits_ref.0.x = point.x - 1; }
-                if point.y < limits_ref.0.y + 1 { limits_ref.0.y = point.y - 1; }
-                if point.x > limits_ref.1.x - 1 { limits_ref.1.x = point.x + 1; }
-                if point.y > limits_ref.1.y - 1 { limits_ref.1.y = point.y + 1; }
-            }
-        }
-
-        return limits;
+    fn compute_sky_limits(&self) -> Option<BoundingBox<CoordinateUnit>> {
+        return BoundingBox::from_points(self.current_sky.values()).map(|limits| limits.expand(1));
     }
 
     fn get_density(&self) -> f64 {
         let sky_limits = self.compute_sky_limits().unwrap();
-        let sky_width = (sky_limits.1.x - sky_limits.0.x) as f64;
-        let sky_height = (sky_limits.1.y - sky_limits.0.y) as f64;
+        let sky_width = (sky_limits.width() - 1) as f64;
+        let sky_height = (sky_limits.height() - 1) as f64;
 
         let number_points = self.point_definitions.len() as f64;
         
@@ -105,14 +142,29 @@ impl Sky {
 
     pub fn worth_printing(&self) -> bool {
         let sky_limits = self.compute_sky_limits().unwrap();
-        let sky_width = (sky_limits.1.x - sky_limits.0.x) as f64;
-        let sky_height = (sky_limits.1.y - sky_limits.0.y) as f64;
+        let sky_width = (sky_limits.width() - 1) as f64;
+        let sky_height = (sky_limits.height() - 1) as f64;
 
         let number_points = self.point_definitions.len() as f64;
 
         return sky_width + sky_height <= number_points * self.print_size_factor;
     }
 
+    pub fn find_message(&mut self) -> Option<(usize, String)> {
+        let mut message : Option<(usize, f64, String)> = None;
+        while self.worth_running() {
+            self.run_iteration();
+            if !self.worth_printing() { continue; }
+
+            let density = self.get_density();
+            if message.is_none() || message.as_ref().unwrap().1 < density {
+                message = Some((self.iteration, density, self.print_current_sky()));
+            }
+        }
+
+        return message.map(|(iteration, _, current_sky)| (iteration, current_sky));
+    }
+
     pub fn print_current_sky(&self) -> String {
 
         let sky_limits = self.compute_sky_limits().unwrap();
@@ -121,17 +173,181 @@ impl Sky {
             .collect();
 
         let mut final_string : String = String::new();
-        for check_point_y in sky_limits.0.y..=sky_limits.1.y {
-            for check_point_x in sky_limits.0.x..=sky_limits.1.x {
-
-                let check_point = Coordinate2D { x: check_point_x, y: check_point_y };
-                if checked_points.contains(&&check_point) { final_string.push('x'); }
-                else { final_string.push('.'); }
-            }
+        for check_point in sky_limits.points() {
+            if checked_points.contains(&&check_point) { final_string.push('x'); }
+            else { final_string.push('.'); }
 
-            final_string.push('\n')
+            if check_point.x == sky_limits.max.x { final_string.push('\n') }
         }
 
         return final_string;
     }
+}
+
+// ============================================================= EXAMPLES ==============================================================
+
+const EXAMPLE : &str = "\
+position=< 9,  1> velocity=< 0,  2>
+position=< 7,  0> velocity=<-1,  0>
+position=< 3, -2> velocity=<-1,  1>
+position=< 6, 10> velocity=<-2, -1>
+position=< 2, -4> velocity=< 2,  2>
+position=<-6, 10> velocity=< 2, -2>
+position=< 1,  8> velocity=< 1, -1>
+position=< 1,  7> velocity=< 1,  0>
+position=<-3, 11> velocity=< 1, -2>
+position=< 7,  6> velocity=<-1, -1>
+position=<-2,  3> velocity=< 1,  0>
+position=<-4,  3> velocity=< 2,  0>
+position=<10, -3> velocity=<-1,  1>
+position=< 5, 11> velocity=< 1, -2>
+position=< 4,  7> velocity=< 0, -1>
+position=< 8, -2> velocity=< 0,  1>
+position=<15,  0> velocity=<-2,  0>
+position=< 1,  6> velocity=< 1,  0>
+position=< 8,  9> velocity=< 0, -1>
+position=< 3,  3> velocity=<-1,  1>
+position=< 0,  5> velocity=< 0, -1>
+position=<-2,  2> velocity=< 2,  0>
+position=< 5, -2> velocity=< 1,  2>
+position=< 1,  4> velocity=< 2,  1>
+position=<-2,  7> velocity=< 2, -2>
+position=< 3,  6> velocity=<-1, -1>
+position=< 5,  0> velocity=< 1,  0>
+position=<-6,  0> velocity=< 2,  0>
+position=< 5,  9> velocity=< 1, -2>
+position=<14,  7> velocity=<-2,  0>
+position=<-3,  6> velocity=< 2, -1>";
+
+const MESSAGE : &str = "\
+............
+.x...x..xxx.
+.x...x...x..
+.x...x...x..
+.xxxxx...x..
+.x...x...x..
+.x...x...x..
+.x...x...x..
+.x...x..xxx.
+............
+";
+
+// ====================================================== SOLUTION IMPLEMENTATION ======================================================
+
+impl Day10 {
+    fn find_message(&self, point_definitions: &Vec<PointDefinition>) -> Result<(usize, String), Day10Error> {
+        let mut sky : Sky = Sky::new(point_definitions, self.queue_capacity, self.print_size_factor)?;
+        return sky.find_message().ok_or(Day10Error::NoMessage);
+    }
+}
+
+impl Default for Day10 {
+    fn default() -> Day10 { Day10 { queue_capacity: QUEUE_CAPACITY, print_size_factor: PRINT_SIZE_FACTOR } }
+}
+
+impl Solution for Day10 {
+    const DAY : DayNumber = 10;
+    const TITLE : &'static str = "The Stars Align";
+
+    type Input = Vec<PointDefinition>;
+    type Part1 = String;
+    type Part2 = usize;
+    type Error = Day10Error;
+
+    fn parse(&self, input: &Input) -> Result<Vec<PointDefinition>, Day10Error> {
+        return input.lines().iter()
+            .map(|point_definition| PointDefinition::from_string(point_definition))
+            .collect();
+    }
+
+    fn part1(&self, point_definitions: &Vec<PointDefinition>) -> Result<String, Day10Error> {
+        let (_, message) = self.find_message(point_definitions)?;
+        return Ok(message);
+    }
+
+    fn part2(&self, point_definitions: &Vec<PointDefinition>) -> Result<usize, Day10Error> {
+        let (iteration, _) = self.find_message(point_definitions)?;
+        return Ok(iteration);
+    }
+
+    fn examples() -> Vec<Example<Day10>> {
+        return vec![
+            Example::new(EXAMPLE, Day10 { queue_capacity: QUEUE_CAPACITY, print_size_factor: 1.0 }).part1(MESSAGE).part2("3"),
+        ];
+    }
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use generate::SyntheticRng;
+    use proptest::prelude::*;
+
+    fn example_solution() -> Day10 {
+        return Day10 { queue_capacity: QUEUE_CAPACITY, print_size_factor: 1.0 };
+    }
+
+    #[test]
+    fn shows_message_in_the_sky() {
+        let point_definitions : Vec<PointDefinition> = example_solution().parse(&Input::from_string("example", EXAMPLE)).unwrap();
+        assert_eq!(example_solution().part1(&point_definitions).unwrap(), MESSAGE);
+    }
+
+    #[test]
+    fn waits_for_message_to_appear() {
+        let point_definitions : Vec<PointDefinition> = example_solution().parse(&Input::from_string("example", EXAMPLE)).unwrap();
+        assert_eq!(example_solution().part2(&point_definitions).unwrap(), 3);
+    }
+
+    /// Densest printable sky over the first `horizon` seconds (the earliest one among ties), moving every point without stopping early.
+    fn brute_force_message_time(point_definitions: &Vec<PointDefinition>, print_size_factor: f64, horizon: usize) -> Option<usize> {
+        let number_points : f64 = point_definitions.len() as f64;
+        let mut densest : Option<(usize, f64)> = None;
+        for seconds in 1..=horizon {
+            let positions : Vec<Coordinate2D> = point_definitions.iter()
+                .map(|point_definition| point_definition.position + point_definition.velocity * seconds as CoordinateUnit)
+                .collect();
+            let limits : BoundingBox<CoordinateUnit> = BoundingBox::from_points(&positions).unwrap().expand(1);
+            let (sky_width, sky_height) : (f64, f64) = ((limits.width() - 1) as f64, (limits.height() - 1) as f64);
+
+            let density : f64 = number_points / (sky_width * sky_height);
+            if sky_width + sky_height > number_points * print_size_factor { continue; }
+            if densest.is_none() || densest.unwrap().1 < density { densest = Some((seconds, density)); }
+        }
+
+        return densest.map(|(seconds, _)| seconds);
+    }
+
+    proptest! {
+        #[test]
+        fn stops_once_points_converged(seed in any::<u64>(), letters in 1..4_usize, seconds in 1..300_usize) {
+            let point_cloud : String = generate::point_cloud(&mut SyntheticRng::new(seed), letters, seconds);
+            let point_definitions : Vec<PointDefinition> = Day10::default().parse(&Input::from_string("generated", &point_cloud)).unwrap();
+
+            prop_assert_eq!(Day10::default().part2(&point_definitions).unwrap(), seconds);
+            prop_assert_eq!(brute_force_message_time(&point_definitions, PRINT_SIZE_FACTOR, 2 * seconds + QUEUE_CAPACITY), Some(seconds));
+        }
+
+        #[test]
+        fn stopping_matches_brute_force(seconds in 1..200_i64, points in prop::collection::vec((0..4_i64, 0..4_i64, -4..=4_i64, -4..=4_i64), 10..40)) {
+            // Points pass anywhere within an area small enough to be printed at the same time, with any velocity (including none)
+            let point_definitions : Vec<PointDefinition> = points.into_iter()
+                .map(|(x, y, velocity_x, velocity_y)| (Coordinate2D::new(x, y), Coordinate2D::new(velocity_x, velocity_y)))
+                .map(|(gathering, velocity)| PointDefinition { position: gathering - velocity * seconds, velocity: velocity })
+                .collect();
+            let solution : Day10 = example_solution();
+
+            let message_time : Option<usize> = solution.find_message(&point_definitions).ok().map(|(seconds, _)| seconds);
+            prop_assert!(message_time.is_some());
+            prop_assert_eq!(message_time, brute_force_message_time(&point_definitions, solution.print_size_factor, 3 * seconds as usize + 50));
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_point_definitions() {
+        assert!(matches!(PointDefinition::from_string("position=<1, 2>"), Err(Day10Error::InvalidPointDefinition(_))));
+        assert!(matches!(Day10::default().part1(&Vec::new()), Err(Day10Error::NoPoints)));
+    }
 }
\ No newline at end of file
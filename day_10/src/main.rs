@@ -1,41 +1,34 @@
-use regex::Regex;
+use common::{CountingAllocator, OutputFormat, Solution, check_example, example_from_args, init_logging, print_json_answers, print_solution_stats, solution_from_args, stats_from_args, verbosity_from_args};
+use read::Input;
+use day_10::Day10;
 
-mod read;
-mod lib;
-
-use lib::{Coordinate2D, PointDefinition, Sky};
+#[global_allocator]
+static ALLOCATOR : CountingAllocator = CountingAllocator;
 
 fn main() {
 
-    let point_definitions_string = read::read_lines("input.txt".to_owned());
-
-    let point_definition_regex : Regex = Regex::new(r"position=<\s*(-?\d+),\s*(-?\d+)> velocity=<\s*(-?\d+),\s*(-?\d+)>").unwrap();
-    let point_definitions : Vec<PointDefinition> = point_definitions_string.into_iter()
-        .map(|point_definition| {
-            let point_capture = point_definition_regex.captures(&point_definition).unwrap();
+    init_logging(verbosity_from_args());
+    if let Some(example) = example_from_args().unwrap() {
+        std::process::exit(if check_example::<Day10>(example).unwrap() { 0 } else { 1 });
+    }
 
-            let position_x : i64 = point_capture.get(1).unwrap().as_str().parse().unwrap();
-            let position_y : i64 = point_capture.get(2).unwrap().as_str().parse().unwrap();
-            let velocity_x : i64 = point_capture.get(3).unwrap().as_str().parse().unwrap();
-            let velocity_y : i64 = point_capture.get(4).unwrap().as_str().parse().unwrap();
+    let input = Input::from_path("input.txt").unwrap();
+    let solution : Day10 = solution_from_args().unwrap();
+    if OutputFormat::from_args().unwrap() == OutputFormat::Json {
+        return print_json_answers(&solution, &input).unwrap();
+    }
+    if stats_from_args() {
+        return print_solution_stats(&solution, &input).unwrap();
+    }
 
-            let position : Coordinate2D = Coordinate2D { x: position_x, y: position_y };
-            let velocity : Coordinate2D = Coordinate2D { x: velocity_x, y: velocity_y };
+    let point_definitions = solution.parse(&input).unwrap();
 
-            return PointDefinition { position: position, velocity: velocity }; })
-        .collect();
-    let mut sky : Sky = Sky::new(&point_definitions, 10, 0.25);
-    
-    while sky.worth_running() {
-        sky.run_iteration();
-        let current_iteration = sky.get_current_iteration();
-        
-        if sky.worth_printing() {
-            let current_sky = sky.print_current_sky();
+    // Part 1
+    let message = solution.part1(&point_definitions).unwrap();
+    println!("🌟 Message shown in the sky (Part 1):");
+    println!("{}", message);
 
-            println!("🌟 Iteration: {}", current_iteration);
-            println!("{}", current_sky);
-            println!();
-        }
-    }
+    // Part 2
+    let iteration = solution.part2(&point_definitions).unwrap();
+    println!("🌟 Message shown after '{}' iterations (Part 2)", iteration);
 }
\ No newline at end of file
@@ -1,16 +1,30 @@
-use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use tracing::trace;
+
+use common::{BLACK, BoundingBox, DayNumber, Example, Grid, Image, Point, Solution, heat_color};
+use read::{Input, ReadError};
+
+// ======================================================= CONSTANTS DEFINITIONS =======================================================
+
+pub const GRID_SIZE : CoordinateUnit = 300;
+
+/// Largest serial number accepted, so that power levels never overflow (puzzle serial numbers have four digits).
+const MAX_SERIAL_NUMBER : GridSerialNumber = 1_000_000;
+
+const MIN_FUEL_LEVEL : FuelLevel = -5;
+const MAX_FUEL_LEVEL : FuelLevel = 4;
+const RENDER_SCALE : usize = 2;
 
 // ======================================================== STRUCTS DEFINITIONS ========================================================
 
 pub type GridSerialNumber = u64;
-type CoordinateUnit = u64;
-type FuelLevel = i64;
+pub type CoordinateUnit = u64;
+pub type FuelLevel = i64;
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
-pub struct Coordinate2D {
-    x: CoordinateUnit,
-    y: CoordinateUnit,
-}
+pub type Coordinate2D = Point<CoordinateUnit>;
 
 struct FuelCell {
     position: Coordinate2D,
@@ -19,20 +33,75 @@ struct FuelCell {
 
 pub struct FuelGrid {
     serial_number: GridSerialNumber,
-    top_left: Coordinate2D,
-    bottom_right: Coordinate2D,
-    fuel_grid: HashMap<Coordinate2D, FuelCell>
+    limits: BoundingBox<CoordinateUnit>,
+    fuel_grid: Grid<FuelCell, CoordinateUnit>
+}
+
+#[derive(Serialize)]
+pub struct FuelSquare {
+    pub position: Coordinate2D,
+    pub fuel_level: FuelLevel,
+}
+
+#[derive(Serialize)]
+pub struct SizedFuelSquare {
+    pub position: Coordinate2D,
+    pub size: CoordinateUnit,
+    pub fuel_level: FuelLevel,
+}
+
+#[derive(Debug)]
+pub enum Day11Error {
+    Read(ReadError),
+    MissingSerialNumber,
+    InvalidSerialNumber(GridSerialNumber),
+    EmptyGrid,
+    SquareTooLarge { size: CoordinateUnit },
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Day11 {
+    pub grid_size: CoordinateUnit,
 }
 
 // ====================================================== STRUCTS IMPLEMENTATIONS ======================================================
 
-impl Coordinate2D {
-    pub fn new(position_x: CoordinateUnit, position_y: CoordinateUnit) -> Coordinate2D {
-        Coordinate2D { x: position_x, y: position_y }
+impl fmt::Display for FuelSquare {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{},{}", self.position.get_x(), self.position.get_y())
+    }
+}
+
+impl fmt::Display for SizedFuelSquare {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{},{},{}", self.position.get_x(), self.position.get_y(), self.size)
+    }
+}
+
+impl fmt::Display for Day11Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day11Error::Read(error) => write!(formatter, "{}", error),
+            Day11Error::MissingSerialNumber => write!(formatter, "missing grid serial number"),
+            Day11Error::InvalidSerialNumber(serial_number) => write!(formatter, "serial number '{}' is larger than {}", serial_number, MAX_SERIAL_NUMBER),
+            Day11Error::EmptyGrid => write!(formatter, "grid must have at least one fuel cell"),
+            Day11Error::SquareTooLarge { size } => write!(formatter, "square of size '{}' does not fit in the grid", size),
+        }
+    }
+}
+
+impl Error for Day11Error {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Day11Error::Read(error) => Some(error),
+            _ => None,
+        }
     }
+}
 
-    pub fn get_x(&self) -> CoordinateUnit { self.x }
-    pub fn get_y(&self) -> CoordinateUnit { self.y }
+impl From<ReadError> for Day11Error {
+    fn from(error: ReadError) -> Day11Error { Day11Error::Read(error) }
 }
 
 impl FuelCell {
@@ -44,7 +113,7 @@ impl FuelCell {
     fn get_begin_power_level(&self) -> FuelLevel { self.get_rack_id() * self.position.get_y() as FuelLevel }
     fn get_increased_power_level(&self, grid_sn: GridSerialNumber) -> FuelLevel { self.get_begin_power_level() + grid_sn as FuelLevel }
     fn get_set_power_level(&self, grid_sn: GridSerialNumber) -> FuelLevel { self.get_increased_power_level(grid_sn) * self.get_rack_id() }
-    fn get_hundreds_power_level(&self, grid_sn: GridSerialNumber) -> FuelLevel { (self.get_set_power_level(grid_sn) as i64 / 100) % 10 }
+    fn get_hundreds_power_level(&self, grid_sn: GridSerialNumber) -> FuelLevel { (self.get_set_power_level(grid_sn) / 100) % 10 }
     fn get_subtracted_power_level(&self, grid_sn: GridSerialNumber) -> FuelLevel { self.get_hundreds_power_level(grid_sn) - 5 }
 
     fn compute_cell_power_level(&mut self, grid_sn: GridSerialNumber) { self.fuel_level = Some(self.get_subtracted_power_level(grid_sn)) }
@@ -52,81 +121,77 @@ impl FuelCell {
 }
 
 impl FuelGrid {
-    pub fn new(top_left_coordinate: Coordinate2D, bottom_right_coordinate: Coordinate2D, serial_number: GridSerialNumber) -> FuelGrid {
-        let mut fuel_grid : HashMap<Coordinate2D, FuelCell> = HashMap::new();
-        for position_y in top_left_coordinate.get_y()..=bottom_right_coordinate.get_y() {
-            for position_x in top_left_coordinate.get_x()..=bottom_right_coordinate.get_x() {
-                
-                let fuel_position : Coordinate2D = Coordinate2D { x: position_x, y: position_y };
-                let fuel_cell : FuelCell = FuelCell::new(fuel_position);
-                fuel_grid.insert(fuel_position, fuel_cell);
-            }
-        }
+    pub fn new(top_left_coordinate: Coordinate2D, bottom_right_coordinate: Coordinate2D, serial_number: GridSerialNumber) -> Result<FuelGrid, Day11Error> {
+        let limits : BoundingBox<CoordinateUnit> = BoundingBox::new(top_left_coordinate, bottom_right_coordinate);
+        if limits.is_empty() { return Err(Day11Error::EmptyGrid); }
+
+        let fuel_grid : Grid<FuelCell, CoordinateUnit> = Grid::from_fn(limits, |fuel_position| FuelCell::new(fuel_position));
 
-        FuelGrid { serial_number: serial_number, top_left: top_left_coordinate, bottom_right: bottom_right_coordinate, fuel_grid: fuel_grid }
+        Ok(FuelGrid { serial_number: serial_number, limits: limits, fuel_grid: fuel_grid })
     }
 
     pub fn compute_fuel_levels(&mut self) {
-        for (_, fuel_cell) in self.fuel_grid.iter_mut() {
+        for fuel_cell in self.fuel_grid.values_mut() {
             fuel_cell.compute_cell_power_level(self.serial_number);
         }
+
+        trace!("{}", self.print_formatted());
     }
 
-    fn compute_sum_of_areas_mapping(&self) -> HashMap<Coordinate2D, FuelLevel> {
-        let mut sum_areas_mapping : HashMap<Coordinate2D, FuelLevel> = HashMap::new();
-        for position_y in (self.top_left.get_y()..=self.bottom_right.get_y()).rev() {
-            for position_x in (self.top_left.get_x()..=self.bottom_right.get_x()).rev() {
+    /// Summed-area table, with an extra row and column of zeros past the bottom right corner of the grid.
+    fn compute_sum_of_areas_mapping(&self) -> Grid<FuelLevel, CoordinateUnit> {
+        let mapping_limits : BoundingBox<CoordinateUnit> = BoundingBox::new(self.limits.min, self.limits.max + Coordinate2D::new(1, 1));
+        let mut sum_areas_mapping : Grid<FuelLevel, CoordinateUnit> = Grid::new(mapping_limits, 0);
+        for position_y in (self.limits.min.y..=self.limits.max.y).rev() {
+            for position_x in (self.limits.min.x..=self.limits.max.x).rev() {
 
                 let target_position : Coordinate2D = Coordinate2D::new(position_x, position_y);
-                let target_fuel_level : FuelLevel = self.fuel_grid.get(&target_position).unwrap().get_fuel_level().unwrap();
+                let target_fuel_level : FuelLevel = self.fuel_grid[target_position].get_fuel_level().unwrap();
 
-                let area_inclusion_right = *sum_areas_mapping.get(&Coordinate2D::new(position_x + 1, position_y)).unwrap_or(&0);
-                let area_inclusion_bottom = *sum_areas_mapping.get(&Coordinate2D::new(position_x, position_y + 1)).unwrap_or(&0);
-                let area_exclusion_intersection = *sum_areas_mapping.get(&Coordinate2D::new(position_x + 1, position_y + 1)).unwrap_or(&0);
+                let area_inclusion_right = sum_areas_mapping[Coordinate2D::new(position_x + 1, position_y)];
+                let area_inclusion_bottom = sum_areas_mapping[Coordinate2D::new(position_x, position_y + 1)];
+                let area_exclusion_intersection = sum_areas_mapping[Coordinate2D::new(position_x + 1, position_y + 1)];
 
                 let sum_fuel_area = target_fuel_level + area_inclusion_right + area_inclusion_bottom - area_exclusion_intersection;
-                sum_areas_mapping.insert(target_position, sum_fuel_area);
+                sum_areas_mapping[target_position] = sum_fuel_area;
             }
         }
         
         return sum_areas_mapping;
     }
 
-    fn get_area_sum(&self, area_mapping: &HashMap<Coordinate2D, FuelLevel>, position: Coordinate2D, size_x: CoordinateUnit, size_y: CoordinateUnit) -> FuelLevel {
-        let area_total = *area_mapping.get(&position).unwrap();
-        let area_exclusion_right = *area_mapping.get(&Coordinate2D::new(position.get_x() + size_x, position.get_y())).unwrap_or(&0);
-        let area_exclusion_bottom = *area_mapping.get(&Coordinate2D::new(position.get_x(), position.get_y() + size_y)).unwrap_or(&0);
-        let area_exclusion_intersection = *area_mapping.get(&Coordinate2D::new(position.get_x() + size_x, position.get_y() + size_y)).unwrap_or(&0);
+    fn get_area_sum(&self, area_mapping: &Grid<FuelLevel, CoordinateUnit>, position: Coordinate2D, size_x: CoordinateUnit, size_y: CoordinateUnit) -> FuelLevel {
+        let area_total = area_mapping[position];
+        let area_exclusion_right = area_mapping[Coordinate2D::new(position.get_x() + size_x, position.get_y())];
+        let area_exclusion_bottom = area_mapping[Coordinate2D::new(position.get_x(), position.get_y() + size_y)];
+        let area_exclusion_intersection = area_mapping[Coordinate2D::new(position.get_x() + size_x, position.get_y() + size_y)];
 
         return area_total - area_exclusion_right - area_exclusion_bottom + area_exclusion_intersection;
     }
 
-    fn get_max_fuel_square_size_aux(&self, area_mapping: &HashMap<Coordinate2D, FuelLevel>, size: CoordinateUnit) -> (Coordinate2D, FuelLevel) {
+    fn get_max_fuel_square_size_aux(&self, area_mapping: &Grid<FuelLevel, CoordinateUnit>, size: CoordinateUnit) -> (Coordinate2D, FuelLevel) {
 
         let mut current_max : Option<(Coordinate2D, FuelLevel)> = None;
-        for position_y in self.top_left.get_y()..=(self.bottom_right.get_y() - size as CoordinateUnit + 1) {
-            for position_x in self.top_left.get_x()..=(self.bottom_right.get_x() - size as CoordinateUnit + 1) {
-
-                let target_position : Coordinate2D = Coordinate2D::new(position_x, position_y);
-                let current_sum : FuelLevel = self.get_area_sum(&area_mapping, target_position, size, size);
-                if current_max.is_none() || current_max.unwrap().1 < current_sum { current_max = Some((target_position, current_sum)); }
-            }
+        let candidate_positions : BoundingBox<CoordinateUnit> = BoundingBox::new(self.limits.min, self.limits.max + Coordinate2D::new(1, 1) - Coordinate2D::new(size, size));
+        for target_position in candidate_positions.points() {
+            let current_sum : FuelLevel = self.get_area_sum(area_mapping, target_position, size, size);
+            if current_max.is_none() || current_max.unwrap().1 < current_sum { current_max = Some((target_position, current_sum)); }
         }
 
         return current_max.unwrap();
     }
 
-    pub fn get_max_fuel_square_size(&self, size: CoordinateUnit)-> (Coordinate2D, FuelLevel) {
+    pub fn get_max_fuel_square_size(&self, size: CoordinateUnit)-> Result<(Coordinate2D, FuelLevel), Day11Error> {
+        if size == 0 || size > std::cmp::min(self.limits.width(), self.limits.height()) { return Err(Day11Error::SquareTooLarge { size: size }); }
+
         let area_mapping = self.compute_sum_of_areas_mapping();
-        return self.get_max_fuel_square_size_aux(&area_mapping, size);
+        return Ok(self.get_max_fuel_square_size_aux(&area_mapping, size));
     }
     
     pub fn get_max_fuel_square_any(&self) -> (Coordinate2D, CoordinateUnit, FuelLevel) {
         let area_mapping = self.compute_sum_of_areas_mapping();
 
-        let max_size_possible_x : CoordinateUnit = self.bottom_right.get_x() - self.top_left.get_x() + 1;
-        let max_size_possible_y : CoordinateUnit = self.bottom_right.get_y() - self.top_left.get_y() + 1;
-        let max_size_possible = std::cmp::min(max_size_possible_x, max_size_possible_y);
+        let max_size_possible = std::cmp::min(self.limits.width(), self.limits.height());
 
         return (1..=max_size_possible)
             .map(|size| (size, self.get_max_fuel_square_size_aux(&area_mapping, size)))
@@ -136,24 +201,160 @@ impl FuelGrid {
     }
 
 
-    pub fn _print_formatted(&self) -> String {
+    pub fn print_formatted(&self) -> String {
 
         let mut final_string : String = format!("⚡ Grid for a serial number of '{}':\n", self.serial_number);
-        for position_y in self.top_left.get_y()..=self.bottom_right.get_y() {
-            for position_x in self.top_left.get_x()..=self.bottom_right.get_x() {
-                
-                let check_position : Coordinate2D = Coordinate2D::new(position_x, position_y);
-                let fuel_level_option : Option<FuelLevel> = self.fuel_grid.get(&check_position).unwrap().get_fuel_level();
-                match fuel_level_option {
-                    Some(fuel_level) if fuel_level >= 0 => final_string.push_str(&format!("+{} ", fuel_level)),
-                    Some(fuel_level) => final_string.push_str(&format!("{} ", fuel_level)),
-                    None => final_string.push_str("?? "),
-                }
+        for (check_position, fuel_cell) in self.fuel_grid.iter() {
+            match fuel_cell.get_fuel_level() {
+                Some(fuel_level) if fuel_level >= 0 => final_string.push_str(&format!("+{} ", fuel_level)),
+                Some(fuel_level) => final_string.push_str(&format!("{} ", fuel_level)),
+                None => final_string.push_str("?? "),
             }
 
-            final_string.push('\n');
+            if check_position.x == self.limits.max.x { final_string.push('\n'); }
         }
 
         return final_string;
     }
+
+    /// Heatmap of the fuel level of every cell, from the coldest (-5) to the hottest (+4), cells not computed yet in black.
+    pub fn render_heatmap(&self) -> Image {
+        return Image::from_grid(&self.fuel_grid, |_, fuel_cell| match fuel_cell.get_fuel_level() {
+            Some(fuel_level) => heat_color((fuel_level - MIN_FUEL_LEVEL) as f64 / (MAX_FUEL_LEVEL - MIN_FUEL_LEVEL) as f64),
+            None => BLACK,
+        }).scale(RENDER_SCALE);
+    }
+}
+
+// ====================================================== SOLUTION IMPLEMENTATION ======================================================
+
+impl Default for Day11 {
+    fn default() -> Day11 { Day11 { grid_size: GRID_SIZE } }
+}
+
+impl Solution for Day11 {
+    const DAY : DayNumber = 11;
+    const TITLE : &'static str = "Chronal Charge";
+
+    type Input = FuelGrid;
+    type Part1 = FuelSquare;
+    type Part2 = SizedFuelSquare;
+    type Error = Day11Error;
+
+    fn parse(&self, input: &Input) -> Result<FuelGrid, Day11Error> {
+        let grid_serial_number : GridSerialNumber = *input.parse_lines::<GridSerialNumber>()?.first().ok_or(Day11Error::MissingSerialNumber)?;
+        if grid_serial_number > MAX_SERIAL_NUMBER { return Err(Day11Error::InvalidSerialNumber(grid_serial_number)); }
+        let mut fuel_grid : FuelGrid = FuelGrid::new(Coordinate2D::new(1, 1), Coordinate2D::new(self.grid_size, self.grid_size), grid_serial_number)?;
+        fuel_grid.compute_fuel_levels();
+
+        return Ok(fuel_grid);
+    }
+
+    fn part1(&self, fuel_grid: &FuelGrid) -> Result<FuelSquare, Day11Error> {
+        let (position, fuel_level) = fuel_grid.get_max_fuel_square_size(3)?;
+        return Ok(FuelSquare { position: position, fuel_level: fuel_level });
+    }
+
+    fn part2(&self, fuel_grid: &FuelGrid) -> Result<SizedFuelSquare, Day11Error> {
+        let (position, size, fuel_level) = fuel_grid.get_max_fuel_square_any();
+        return Ok(SizedFuelSquare { position: position, size: size, fuel_level: fuel_level });
+    }
+
+    fn render(&self, fuel_grid: &FuelGrid) -> Result<Vec<Image>, Day11Error> {
+        return Ok(vec![fuel_grid.render_heatmap()]);
+    }
+
+    fn examples() -> Vec<Example<Day11>> {
+        return vec![
+            Example::new("18", Day11::default()).part1("33,45").part2("90,269,16"),
+            Example::new("42", Day11::default()).part1("21,61").part2("232,251,12"),
+        ];
+    }
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn parse_serial_number(serial_number: GridSerialNumber) -> FuelGrid {
+        return Day11::default().parse(&Input::from_string("example", &serial_number.to_string())).unwrap();
+    }
+
+    #[test]
+    fn computes_cell_power_levels() {
+        let cells : [(CoordinateUnit, CoordinateUnit, GridSerialNumber, FuelLevel); 4] = [(3, 5, 8, 4), (122, 79, 57, -5), (217, 196, 39, 0), (101, 153, 71, 4)];
+        for (position_x, position_y, serial_number, fuel_level) in cells.into_iter() {
+            let mut fuel_cell : FuelCell = FuelCell::new(Coordinate2D::new(position_x, position_y));
+            fuel_cell.compute_cell_power_level(serial_number);
+            assert_eq!(fuel_cell.get_fuel_level(), Some(fuel_level));
+        }
+    }
+
+    #[test]
+    fn finds_largest_three_by_three_square() {
+        let fuel_square : FuelSquare = Day11::default().part1(&parse_serial_number(18)).unwrap();
+        assert_eq!((fuel_square.to_string(), fuel_square.fuel_level), ("33,45".to_owned(), 29));
+
+        let fuel_square : FuelSquare = Day11::default().part1(&parse_serial_number(42)).unwrap();
+        assert_eq!((fuel_square.to_string(), fuel_square.fuel_level), ("21,61".to_owned(), 30));
+    }
+
+    #[test]
+    fn finds_largest_square_of_any_size() {
+        let fuel_square : SizedFuelSquare = Day11::default().part2(&parse_serial_number(18)).unwrap();
+        assert_eq!((fuel_square.to_string(), fuel_square.fuel_level), ("90,269,16".to_owned(), 113));
+
+        let fuel_square : SizedFuelSquare = Day11::default().part2(&parse_serial_number(42)).unwrap();
+        assert_eq!((fuel_square.to_string(), fuel_square.fuel_level), ("232,251,12".to_owned(), 119));
+    }
+
+    /// Best square of the given size (the first one in reading order among ties), summing every cell of every candidate.
+    fn brute_force_square(fuel_grid: &FuelGrid, size: CoordinateUnit) -> (Coordinate2D, FuelLevel) {
+        let mut best_square : Option<(Coordinate2D, FuelLevel)> = None;
+        for position_y in fuel_grid.limits.min.y..=fuel_grid.limits.max.y + 1 - size {
+            for position_x in fuel_grid.limits.min.x..=fuel_grid.limits.max.x + 1 - size {
+                let fuel_level : FuelLevel = (position_y..position_y + size)
+                    .flat_map(|cell_y| (position_x..position_x + size).map(move |cell_x| Coordinate2D::new(cell_x, cell_y)))
+                    .map(|cell_position| fuel_grid.fuel_grid[cell_position].get_fuel_level().unwrap())
+                    .sum();
+                if best_square.is_none() || best_square.unwrap().1 < fuel_level { best_square = Some((Coordinate2D::new(position_x, position_y), fuel_level)); }
+            }
+        }
+
+        return best_square.unwrap();
+    }
+
+    proptest! {
+        #[test]
+        fn summed_area_table_matches_brute_force(serial_number in 0..10_000_u64, width in 1..16_u64, height in 1..16_u64, size in 1..16_u64) {
+            let mut fuel_grid : FuelGrid = FuelGrid::new(Coordinate2D::new(1, 1), Coordinate2D::new(width, height), serial_number).unwrap();
+            fuel_grid.compute_fuel_levels();
+
+            match fuel_grid.get_max_fuel_square_size(size) {
+                Ok(square) => prop_assert_eq!(square, brute_force_square(&fuel_grid, size)),
+                Err(_) => prop_assert!(size > width.min(height)),
+            }
+
+            let best_fuel_level : FuelLevel = (1..=width.min(height)).map(|size| brute_force_square(&fuel_grid, size).1).max().unwrap();
+            prop_assert_eq!(fuel_grid.get_max_fuel_square_any().2, best_fuel_level);
+        }
+    }
+
+    #[test]
+    fn rejects_squares_larger_than_the_grid() {
+        let fuel_grid : FuelGrid = Day11 { grid_size: 2 }.parse(&Input::from_string("example", "18")).unwrap();
+        assert!(matches!(Day11 { grid_size: 2 }.part1(&fuel_grid), Err(Day11Error::SquareTooLarge { size: 3 })));
+        assert!(matches!(Day11::default().parse(&Input::from_string("example", "18446744073709551615")), Err(Day11Error::InvalidSerialNumber(_))));
+    }
+
+    #[test]
+    fn renders_fuel_heatmap() {
+        let fuel_grid : FuelGrid = Day11 { grid_size: 5 }.parse(&Input::from_string("example", "8")).unwrap();
+        let image : Image = fuel_grid.render_heatmap();
+        assert_eq!((image.get_width(), image.get_height()), (10, 10));
+        assert_eq!(image.get_pixel(4, 8), Some(heat_color(1.0)));
+    }
 }
\ No newline at end of file
@@ -1,26 +1,38 @@
-mod read;
-mod lib;
+use common::{CountingAllocator, OutputFormat, Solution, check_example, example_from_args, init_logging, print_json_answers, print_solution_stats, render_path_from_args, save_render, solution_from_args, stats_from_args, verbosity_from_args};
+use read::Input;
+use day_11::Day11;
 
-use lib::{GridSerialNumber, Coordinate2D, FuelGrid};
+#[global_allocator]
+static ALLOCATOR : CountingAllocator = CountingAllocator;
 
 fn main() {
 
-    let grid_serial_number : GridSerialNumber = *read::read_int_lines("input.txt".to_owned()).get(0).unwrap() as GridSerialNumber;
+    init_logging(verbosity_from_args());
+    if let Some(example) = example_from_args().unwrap() {
+        std::process::exit(if check_example::<Day11>(example).unwrap() { 0 } else { 1 });
+    }
 
-    let top_left_coordinate : Coordinate2D = Coordinate2D::new(1, 1);
-    let bottom_right_coordinate : Coordinate2D = Coordinate2D::new(300, 300);
+    let input = Input::from_path("input.txt").unwrap();
+    let solution : Day11 = solution_from_args().unwrap();
+    if let Some(render_path) = render_path_from_args().unwrap() {
+        for frame_path in save_render(&solution, &input, &render_path).unwrap() {
+            eprintln!("🖼️  Rendered '{}'", frame_path.display());
+        }
+    }
+    if OutputFormat::from_args().unwrap() == OutputFormat::Json {
+        return print_json_answers(&solution, &input).unwrap();
+    }
+    if stats_from_args() {
+        return print_solution_stats(&solution, &input).unwrap();
+    }
 
-    let mut fuel_grid : FuelGrid = FuelGrid::new(top_left_coordinate, bottom_right_coordinate, grid_serial_number);
-    fuel_grid.compute_fuel_levels();
-
-    // println!("{}", fuel_grid._print_formatted());
-    // println!();
+    let fuel_grid = solution.parse(&input).unwrap();
 
     // Part 1
-    let max_fuel_grid = fuel_grid.get_max_fuel_square_size(3);
-    println!("⚡ Max fuel level found in '({}, {})' with value of '{}' (Part 1)", max_fuel_grid.0.get_x(), max_fuel_grid.0.get_y(), max_fuel_grid.1);
+    let max_fuel_grid = solution.part1(&fuel_grid).unwrap();
+    println!("⚡ Max fuel level found in '({}, {})' with value of '{}' (Part 1)", max_fuel_grid.position.get_x(), max_fuel_grid.position.get_y(), max_fuel_grid.fuel_level);
     
     // Part 2
-    let max_fuel_grid = fuel_grid.get_max_fuel_square_any();
-    println!("⚡ Max fuel level found in '({}, {}, {})' with value of '{}' (Part 1)", max_fuel_grid.0.get_x(), max_fuel_grid.0.get_y(), max_fuel_grid.1, max_fuel_grid.2);
+    let max_fuel_grid = solution.part2(&fuel_grid).unwrap();
+    println!("⚡ Max fuel level found in '({}, {}, {})' with value of '{}' (Part 2)", max_fuel_grid.position.get_x(), max_fuel_grid.position.get_y(), max_fuel_grid.size, max_fuel_grid.fuel_level);
 }
\ No newline at end of file
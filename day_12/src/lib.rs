@@ -1,4 +1,18 @@
 use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use regex::Regex;
+use serde::Deserialize;
+use tracing::{debug, trace};
+
+use common::{DayNumber, Example, Solution};
+use read::Input;
+
+// ======================================================= CONSTANTS DEFINITIONS =======================================================
+
+pub const FIRST_PART_NUMBER_ITERATIONS : usize = 20;
+pub const SECOND_PART_NUMBER_ITERATIONS : usize = 50000000000;
 
 // ======================================================== STRUCTS DEFINITIONS ========================================================
 
@@ -12,36 +26,73 @@ pub struct Pot {
     state: PotState,
 }
 
+#[derive(Clone)]
 pub struct Rule {
     requesits: Vec<(PotPosition, PotState)>,
     result: PotState,
 }
 
+#[derive(Clone)]
 pub struct Garden {
     current_iteration: usize,
     pots: HashMap<PotPosition, Pot>,
     rules: Vec<Rule>
 }
 
+#[derive(Debug)]
+pub enum Day12Error {
+    MissingInitialState,
+    InvalidInitialState(String),
+    InvalidRule(String),
+    InvalidPotState(char),
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Day12 {
+    pub first_part_iterations: usize,
+    pub second_part_iterations: usize,
+}
+
 // ======================================================== AUXILIARY FUNCTIONS== ======================================================
 
-fn convert_char_to_pot_state(char_state: char) -> PotState {
+fn convert_char_to_pot_state(char_state: char) -> Result<PotState, Day12Error> {
     match char_state {
-        '#' => PotState::WithPlant,
-        '.' => PotState::WithoutPlant,
-        _   => panic!("🚨 Pot state '{}' not recognized!", char_state)
+        '#' => Ok(PotState::WithPlant),
+        '.' => Ok(PotState::WithoutPlant),
+        _   => Err(Day12Error::InvalidPotState(char_state))
     }
 }
 
+pub fn parse_garden(input_read: Vec<String>) -> Result<Garden, Day12Error> {
+    let mut input_lines = input_read.into_iter();
+    let initial_state_line = input_lines.next().ok_or(Day12Error::MissingInitialState)?;
+    let rule_lines : Vec<String> = input_lines.filter(|line| !line.trim().is_empty()).collect();
+
+    let initial_state_regex : Regex = Regex::new(r"initial state: ([#|\.]+)").unwrap();
+    let rule_regex : Regex = Regex::new(r"([#|\.]+) => ([#|\.])").unwrap();
+
+    let initial_pots : Vec<Pot> = initial_state_regex.captures(&initial_state_line)
+        .and_then(|initial_state_capture| initial_state_capture.get(1))
+        .ok_or_else(|| Day12Error::InvalidInitialState(initial_state_line.clone()))?
+        .as_str().chars()
+        .map(|pot_state_char| Pot::new(pot_state_char))
+        .collect::<Result<_, _>>()?;
+    let rules : Vec<Rule> = rule_lines.iter()
+        .map(|rule_string| rule_regex.captures(rule_string).ok_or_else(|| Day12Error::InvalidRule(rule_string.clone())))
+        .map(|rule_capture| rule_capture.map(|rule_capture| (rule_capture.get(1).unwrap().as_str(), rule_capture.get(2).unwrap().as_str())))
+        .map(|rule_strings| rule_strings.and_then(|(rule_requisit_str, rule_result_str)| Rule::new(rule_requisit_str.chars().collect(), rule_result_str.chars().nth(0).unwrap())))
+        .collect::<Result<_, _>>()?;
+
+    return Ok(Garden::new(initial_pots, rules));
+}
+
 // ====================================================== STRUCTS IMPLEMENTATIONS ======================================================
 
 impl Pot {
-    pub fn new(pot_state: char) -> Pot {
-        let pot_state : PotState = match pot_state {
-            '#' => PotState::WithPlant,
-            '.' => PotState::WithoutPlant,
-            _   => panic!("🚨 Pot state '{}' not recognized!", pot_state)};
-        Pot { state: pot_state }
+    pub fn new(pot_state: char) -> Result<Pot, Day12Error> {
+        let pot_state : PotState = convert_char_to_pot_state(pot_state)?;
+        Ok(Pot { state: pot_state })
     }
 
     fn new_with_state(pot_state: PotState) -> Pot {
@@ -59,16 +110,29 @@ impl Pot {
 }
 
 impl Rule {
-    pub fn new(requisit_chars: Vec<char>, result_char: char) -> Rule {
+    pub fn new(requisit_chars: Vec<char>, result_char: char) -> Result<Rule, Day12Error> {
         let origin : PotPosition = requisit_chars.len() as PotPosition / 2;
         let requesits : Vec<(PotPosition, PotState)> = requisit_chars.into_iter().enumerate()
-            .map(|(index, requisit_char)| (index as PotPosition - origin, convert_char_to_pot_state(requisit_char)))
-            .collect();
-        let result : PotState = convert_char_to_pot_state(result_char);
-        return Rule { requesits: requesits, result: result };
+            .map(|(index, requisit_char)| convert_char_to_pot_state(requisit_char).map(|state| (index as PotPosition - origin, state)))
+            .collect::<Result<_, _>>()?;
+        let result : PotState = convert_char_to_pot_state(result_char)?;
+        return Ok(Rule { requesits: requesits, result: result });
     }
 }
 
+impl fmt::Display for Day12Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day12Error::MissingInitialState => write!(formatter, "missing initial state of the pots"),
+            Day12Error::InvalidInitialState(line) => write!(formatter, "initial state '{}' could not be parsed", line),
+            Day12Error::InvalidRule(line) => write!(formatter, "rule '{}' could not be parsed", line),
+            Day12Error::InvalidPotState(pot_state) => write!(formatter, "pot state '{}' not recognized", pot_state),
+        }
+    }
+}
+
+impl Error for Day12Error {}
+
 impl Garden {
     pub fn new(initial_state: Vec<Pot>, rules: Vec<Rule>) -> Garden {
         Garden {
@@ -82,28 +146,23 @@ impl Garden {
 
     pub fn get_current_iteration(&self) -> usize { self.current_iteration }
 
-    fn get_first_check_position(&self) -> PotPosition {
+    fn get_check_limits(&self) -> Option<(PotPosition, PotPosition)> {
         let max_rule_size : PotPosition = self.rules.iter()
             .map(|rule| rule.requesits.len())
-            .max().unwrap() as PotPosition;
-        return self.pots.iter()
+            .max().unwrap_or(0) as PotPosition;
+        let first_plant : PotPosition = *self.pots.iter()
             .filter(|(_, pot)| pot.matches_state(PotState::WithPlant))
             .map(|(pot_position, _)| pot_position)
-            .min().unwrap() - (max_rule_size / 2);
-    }
-
-    fn get_last_check_position(&self) -> PotPosition {
-        let max_rule_size : PotPosition = self.rules.iter()
-            .map(|rule| rule.requesits.len())
-            .max().unwrap() as PotPosition;
-        return self.pots.iter()
+            .min()?;
+        let last_plant : PotPosition = *self.pots.iter()
             .filter(|(_, pot)| pot.matches_state(PotState::WithPlant))
             .map(|(pot_position, _)| pot_position)
-            .max().unwrap() + (max_rule_size / 2);
+            .max()?;
+
+        return Some((first_plant - (max_rule_size / 2), last_plant + (max_rule_size / 2)));
     }
 
-    fn translate_pots(&mut self, translate_first_to: PotPosition) {
-        let first_position : PotPosition = self.get_first_check_position();
+    fn translate_pots(&mut self, first_position: PotPosition, translate_first_to: PotPosition) {
         let translation : PotPosition = translate_first_to - first_position;
         let new_pots : HashMap<PotPosition, Pot> = self.pots.iter()
             .map(|(&pot_position, &pot)| (pot_position + translation, pot.clone()))
@@ -114,9 +173,8 @@ impl Garden {
 
     fn run_iteration(&mut self) {
 
-        // Get Limits for Rule Testing
-        let first_position : PotPosition = self.get_first_check_position();
-        let last_position : PotPosition = self.get_last_check_position();
+        // Get Limits for Rule Testing (a garden without plants stays empty)
+        let (first_position, last_position) = self.get_check_limits().unwrap_or((0, -1));
         
         // Iterate and check for rules
         let mut new_set_of_pots : HashMap<PotPosition, Pot> = HashMap::new();
@@ -148,17 +206,24 @@ impl Garden {
         self.current_iteration = self.current_iteration + 1;
         self.pots = new_set_of_pots;
 
+        debug!(iteration = self.current_iteration, plants = self.pots.len(), "garden grown");
+        trace!("{}", self.print_iteration());
     }
 
     pub fn run_until_iteration(&mut self, iteration: usize) {
 
         let mut already_verified : HashMap<String, (usize, PotPosition)> = HashMap::new();
-        while self.get_current_iteration() != iteration {
+        while self.get_current_iteration() < iteration {
 
             // Run iteration
             self.run_iteration();
+            let (first_position, last_position) = match self.get_check_limits() {
+                Some(check_limits) => check_limits,
+                None => { self.current_iteration = iteration; break; }
+            };
+
             // Get current iteration code
-            let current_code : String = self.get_iteration_code();
+            let current_code : String = self.get_iteration_code(first_position, last_position);
 
             // Deal with already verified
             let matched_iteration_option = already_verified.get(&current_code);
@@ -170,12 +235,12 @@ impl Garden {
                 let number_of_iterations_missing = iteration - self.current_iteration;
                 let number_of_jumps = number_of_iterations_missing / jump;
                 // Get Translation Jump
-                let first_position = self.get_first_check_position();
                 let translation = first_position - matched_iteration.1;
 
+                debug!(iteration = self.current_iteration, cycle = jump, jumps = number_of_jumps, shift = translation, "cycle found, jumping ahead");
                 self.current_iteration = self.current_iteration + number_of_jumps * jump;
-                self.translate_pots(first_position + number_of_jumps as PotPosition * translation);
-            } else { already_verified.insert(current_code, (self.current_iteration, self.get_first_check_position())); }
+                self.translate_pots(first_position, first_position + number_of_jumps as PotPosition * translation);
+            } else { already_verified.insert(current_code, (self.current_iteration, first_position)); }
         }
     }
 
@@ -186,9 +251,7 @@ impl Garden {
             .sum();
     }
 
-    fn get_iteration_code(&self) -> String {
-        let first_position : PotPosition = self.get_first_check_position();
-        let last_position : PotPosition = self.get_last_check_position();
+    fn get_iteration_code(&self, first_position: PotPosition, last_position: PotPosition) -> String {
         return (first_position..=last_position)
             .map(|pot_position| {
                 match self.pots.get(&pot_position) {
@@ -198,8 +261,123 @@ impl Garden {
             .collect::<String>();
     }
         
-    pub fn _print_iteration(&self) -> String {
-        let first_position : PotPosition = self.get_first_check_position();
-        return format!("🌱 Iteration [{}]: {} (starting on '{}')", self.current_iteration, self.get_iteration_code(), first_position);
+    pub fn print_iteration(&self) -> String {
+        let (first_position, last_position) = self.get_check_limits().unwrap_or((0, -1));
+        return format!("🌱 Iteration [{}]: {} (starting on '{}')", self.current_iteration, self.get_iteration_code(first_position, last_position), first_position);
+    }
+}
+
+// ============================================================= EXAMPLES ==============================================================
+
+const EXAMPLE : &str = "\
+initial state: #..#.#..##......###...###
+
+...## => #
+..#.. => #
+.#... => #
+.#.#. => #
+.#.## => #
+.##.. => #
+.#### => #
+#.#.# => #
+#.### => #
+##.#. => #
+##.## => #
+###.. => #
+###.# => #
+####. => #";
+
+// ====================================================== SOLUTION IMPLEMENTATION ======================================================
+
+impl Default for Day12 {
+    fn default() -> Day12 {
+        Day12 { first_part_iterations: FIRST_PART_NUMBER_ITERATIONS, second_part_iterations: SECOND_PART_NUMBER_ITERATIONS }
+    }
+}
+
+impl Solution for Day12 {
+    const DAY : DayNumber = 12;
+    const TITLE : &'static str = "Subterranean Sustainability";
+
+    type Input = Garden;
+    type Part1 = i64;
+    type Part2 = i64;
+    type Error = Day12Error;
+
+    fn parse(&self, input: &Input) -> Result<Garden, Day12Error> {
+        return parse_garden(input.lines());
+    }
+
+    fn part1(&self, garden: &Garden) -> Result<i64, Day12Error> {
+        let mut garden : Garden = garden.clone();
+        garden.run_until_iteration(self.first_part_iterations);
+        return Ok(garden.get_iteration_sum_with_plants());
+    }
+
+    fn part2(&self, garden: &Garden) -> Result<i64, Day12Error> {
+        let mut garden : Garden = garden.clone();
+        garden.run_until_iteration(self.second_part_iterations);
+        return Ok(garden.get_iteration_sum_with_plants());
+    }
+
+    fn examples() -> Vec<Example<Day12>> {
+        return vec![
+            Example::new(EXAMPLE, Day12::default()).part1("325"),
+        ];
+    }
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Garden with the given pots and one rule per pattern of five pots, the empty one always leaving the pot empty.
+    fn random_garden(initial_state: &[bool], rule_results: &[bool]) -> Garden {
+        let pot_char = |plant: bool| if plant { '#' } else { '.' };
+        let initial_pots : Vec<Pot> = initial_state.iter().map(|&plant| Pot::new(pot_char(plant)).unwrap()).collect();
+        let rules : Vec<Rule> = rule_results.iter().enumerate().skip(1)
+            .map(|(pattern, &result)| Rule::new((0..5).map(|index| pot_char(pattern & (1 << index) != 0)).collect(), pot_char(result)).unwrap())
+            .collect();
+        return Garden::new(initial_pots, rules);
+    }
+
+    #[test]
+    fn sums_pots_with_plants_after_twenty_generations() {
+        let garden : Garden = Day12::default().parse(&Input::from_string("example", EXAMPLE)).unwrap();
+        assert_eq!(Day12::default().part1(&garden).unwrap(), 325);
+    }
+
+    #[test]
+    fn jumps_over_repeated_generations() {
+        let mut garden : Garden = Day12::default().parse(&Input::from_string("example", EXAMPLE)).unwrap();
+        let mut naive_garden : Garden = garden.clone();
+        for _ in 0..200 { naive_garden.run_iteration(); }
+
+        garden.run_until_iteration(200);
+        assert_eq!(garden.get_iteration_sum_with_plants(), naive_garden.get_iteration_sum_with_plants());
+    }
+
+    proptest! {
+        #[test]
+        fn cycle_jump_matches_naive_generations(initial_state in prop::collection::vec(any::<bool>(), 1..20),
+            rule_results in prop::collection::vec(any::<bool>(), 32), iterations in 0..150_usize) {
+
+            let mut garden : Garden = random_garden(&initial_state, &rule_results);
+            let mut naive_garden : Garden = garden.clone();
+            for _ in 0..iterations { naive_garden.run_iteration(); }
+
+            garden.run_until_iteration(iterations);
+            prop_assert_eq!(garden.get_current_iteration(), iterations);
+            prop_assert_eq!(garden.get_iteration_sum_with_plants(), naive_garden.get_iteration_sum_with_plants());
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_pot_states() {
+        assert!(matches!(Pot::new('x'), Err(Day12Error::InvalidPotState('x'))));
+        assert!(matches!(parse_garden(Vec::new()), Err(Day12Error::MissingInitialState)));
     }
 }
\ No newline at end of file
@@ -1,43 +1,33 @@
-use regex::Regex;
+use common::{CountingAllocator, OutputFormat, Solution, check_example, example_from_args, init_logging, print_json_answers, print_solution_stats, solution_from_args, stats_from_args, verbosity_from_args};
+use read::Input;
+use day_12::Day12;
 
-mod read;
-mod lib;
-
-use lib::{Pot, Rule, Garden};
-
-const FIRST_PART_NUMBER_ITERATIONS : usize = 20;
-const SECOND_PART_NUMBER_ITERATIONS : usize = 50000000000;
+#[global_allocator]
+static ALLOCATOR : CountingAllocator = CountingAllocator;
 
 fn main() {
 
-    let mut input_read : Vec<String> = read::read_lines("input.txt".to_owned());
-    let initial_state_line = input_read.remove(0);
-    input_read.remove(0);
-    let rule_lines = input_read;
-
-    let initial_state_regex : Regex = Regex::new(r"initial state: ([#|\.]+)").unwrap();
-    let rule_regex : Regex = Regex::new(r"([#|\.]+) => ([#|\.])").unwrap();
-
-    let initial_pots : Vec<Pot> = initial_state_regex.captures(&initial_state_line)
-        .unwrap().get(1)
-        .unwrap().as_str().chars().into_iter()
-        .map(|pot_state_char| Pot::new(pot_state_char))
-        .collect();
-    let rules : Vec<Rule> = rule_lines.iter()
-        .map(|rule_string| rule_regex.captures(rule_string).unwrap())
-        .map(|rule_capture| (rule_capture.get(1).unwrap().as_str(), rule_capture.get(2).unwrap().as_str()))
-        .map(|(rule_requisit_str, rule_result_str)| Rule::new(rule_requisit_str.chars().collect(), rule_result_str.chars().nth(0).unwrap()))
-        .collect();
-
-    let mut garden : Garden = Garden::new(initial_pots, rules);
+    init_logging(verbosity_from_args());
+    if let Some(example) = example_from_args().unwrap() {
+        std::process::exit(if check_example::<Day12>(example).unwrap() { 0 } else { 1 });
+    }
+
+    let input = Input::from_path("input.txt").unwrap();
+    let solution : Day12 = solution_from_args().unwrap();
+    if OutputFormat::from_args().unwrap() == OutputFormat::Json {
+        return print_json_answers(&solution, &input).unwrap();
+    }
+    if stats_from_args() {
+        return print_solution_stats(&solution, &input).unwrap();
+    }
+
+    let garden = solution.parse(&input).unwrap();
         
     // Part 1
-    garden.run_until_iteration(FIRST_PART_NUMBER_ITERATIONS);
-    let sum_current_position = garden.get_iteration_sum_with_plants();
-    println!("🌱 After '{}' iterations the pots with plants sum to '{}' (Part 1)", garden.get_current_iteration(), sum_current_position);
+    let sum_current_position = solution.part1(&garden).unwrap();
+    println!("🌱 After '{}' iterations the pots with plants sum to '{}' (Part 1)", solution.first_part_iterations, sum_current_position);
 
-    // Part 1
-    garden.run_until_iteration(SECOND_PART_NUMBER_ITERATIONS);
-    let sum_current_position = garden.get_iteration_sum_with_plants();
-    println!("🌱 After '{}' iterations the pots with plants sum to '{}' (Part 2)", garden.get_current_iteration(), sum_current_position);
+    // Part 2
+    let sum_current_position = solution.part2(&garden).unwrap();
+    println!("🌱 After '{}' iterations the pots with plants sum to '{}' (Part 2)", solution.second_part_iterations, sum_current_position);
 }
\ No newline at end of file
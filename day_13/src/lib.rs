@@ -1,6 +1,19 @@
 use std::collections::HashMap;
-use std::ops::{Add, Sub};
-use core::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+
+use serde::Serialize;
+use tracing::{debug, trace};
+
+use common::{BLACK, BoundingBox, Color, DayNumber, Example, Grid, Image, Point, Solution, palette_color};
+use read::Input;
+
+// ======================================================= CONSTANTS DEFINITIONS =======================================================
+
+const TRACK_COLOR : Color = [96, 96, 96];
+const INTERSECTION_COLOR : Color = [160, 160, 160];
+const CRASH_COLOR : Color = [255, 0, 0];
+const RENDER_SCALE : usize = 4;
 
 // ======================================================== STRUCTS DEFINITIONS ========================================================
 
@@ -12,11 +25,27 @@ enum TrackType { StraightUpDown, StraightLeftRight, DiagonalUpDown, DiagonalDown
 #[derive(Clone, Copy)]
 enum CartDirection { Up, Down, Left, Right }
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Ord, Debug)]
-pub struct Coordinate2D { x: CoordinateUnit, y: CoordinateUnit }
+pub type Coordinate2D = Point<CoordinateUnit>;
+#[derive(Clone)]
 struct Track { track_type: TrackType }
+#[derive(Clone)]
 struct Cart { id: CartID, position: Coordinate2D, cart_direction: CartDirection, current_turns: usize }
-pub struct Simulator { iteration: usize, map: HashMap<Coordinate2D, Track>, carts: HashMap<Coordinate2D, Cart>, collisions: HashMap<Coordinate2D, Vec<usize>> }
+#[derive(Clone)]
+pub struct Simulator { iteration: usize, map: Grid<Option<Track>>, carts: HashMap<Coordinate2D, Cart>, collisions: HashMap<Coordinate2D, Vec<usize>> }
+
+#[derive(Serialize)]
+pub struct CartPosition { pub position: Coordinate2D, pub iteration: usize }
+
+#[derive(Debug)]
+pub enum Day13Error {
+    UnrecognizedSurrounding(Coordinate2D),
+    IllegalMovement(Coordinate2D),
+    OffTrack(Coordinate2D),
+    NotEnoughCarts,
+    NoCartSurvived,
+}
+#[derive(Default)]
+pub struct Day13;
 
 // ======================================================== AUXILIARY FUNCTIONS== ======================================================
 
@@ -41,6 +70,10 @@ fn _convert_char_cart_direction(cart_direction_char: &char) -> Option<CartDirect
     }
 }
 
+fn get_track(map: &Grid<Option<Track>>, position: Coordinate2D) -> Option<&Track> {
+    return map.get(&position).and_then(|track_option| track_option.as_ref());
+}
+
 fn _convert_track_type_char(track_type: &TrackType) -> char {
     match track_type {
         TrackType::StraightUpDown       => '|',
@@ -63,34 +96,26 @@ fn _convert_cart_direction_char(cart_direction: &CartDirection) -> char {
 
 // ====================================================== STRUCTS IMPLEMENTATIONS ======================================================
 
-impl Coordinate2D {
-    fn new(position_x: CoordinateUnit, position_y: CoordinateUnit) -> Coordinate2D {
-        Coordinate2D { x: position_x, y: position_y }
+impl fmt::Display for CartPosition {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{},{}", self.position.get_x(), self.position.get_y())
     }
-
-    pub fn get_x(&self) -> CoordinateUnit { self.x }
-    pub fn get_y(&self) -> CoordinateUnit { self.y }
-}
-
-impl Add for Coordinate2D {
-    type Output = Coordinate2D;
-    fn add(self, other: Coordinate2D) -> Coordinate2D { Coordinate2D { x: self.x + other.x, y: self.y + other.y } }
 }
 
-impl Sub for Coordinate2D {
-    type Output = Coordinate2D;
-    fn sub(self, other: Coordinate2D) -> Coordinate2D { Coordinate2D { x: self.x - other.x, y: self.y - other.y } }
-}
-
-impl PartialOrd for Coordinate2D {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        if self.get_y() == other.get_y() {
-            return Some(self.get_x().cmp(&other.get_x()));
+impl fmt::Display for Day13Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day13Error::UnrecognizedSurrounding(position) => write!(formatter, "not recognized as a valid surrounding for track in '({}, {})'", position.get_x(), position.get_y()),
+            Day13Error::IllegalMovement(position) => write!(formatter, "cart movement is illegal at '({}, {})'", position.get_x(), position.get_y()),
+            Day13Error::OffTrack(position) => write!(formatter, "cart went off track at '({}, {})'", position.get_x(), position.get_y()),
+            Day13Error::NotEnoughCarts => write!(formatter, "not enough carts for a crash to happen"),
+            Day13Error::NoCartSurvived => write!(formatter, "no cart survived the crashes"),
         }
-        Some(self.get_y().cmp(&other.get_y()))
     }
 }
 
+impl Error for Day13Error {}
+
 impl Track {
     fn new(track_type: TrackType) -> Track {
         Track { track_type: track_type }
@@ -124,12 +149,12 @@ impl Cart {
         return self.position + position_variation;
     }
 
-    fn move_cart(&mut self, position: Coordinate2D, position_track_type: TrackType) {
+    fn move_cart(&mut self, position: Coordinate2D, position_track_type: TrackType) -> Result<(), Day13Error> {
 
         self.position = position;
         self.cart_direction = match (self.cart_direction, position_track_type) {
             (CartDirection::Up, TrackType::StraightUpDown)                                      => CartDirection::Up,
-            (CartDirection::Up, TrackType::StraightLeftRight)                                   => panic!("🚨 Cart movement is illegal at '({}, {})'!", position.get_x(), position.get_y()),
+            (CartDirection::Up, TrackType::StraightLeftRight)                                   => return Err(Day13Error::IllegalMovement(position)),
             (CartDirection::Up, TrackType::DiagonalUpDown)                                      => CartDirection::Left,
             (CartDirection::Up, TrackType::DiagonalDownUp)                                      => CartDirection::Right,
             (CartDirection::Up, TrackType::Intersection)        if self.current_turns % 3 == 0  => CartDirection::Left,
@@ -137,14 +162,14 @@ impl Cart {
             (CartDirection::Up, TrackType::Intersection)        if self.current_turns % 3 == 2  => CartDirection::Right,
             
             (CartDirection::Down, TrackType::StraightUpDown)                                    => CartDirection::Down,
-            (CartDirection::Down, TrackType::StraightLeftRight)                                 => panic!("🚨 Cart movement is illegal at '({}, {})'!", position.get_x(), position.get_y()),
+            (CartDirection::Down, TrackType::StraightLeftRight)                                 => return Err(Day13Error::IllegalMovement(position)),
             (CartDirection::Down, TrackType::DiagonalUpDown)                                    => CartDirection::Right,
             (CartDirection::Down, TrackType::DiagonalDownUp)                                    => CartDirection::Left,
             (CartDirection::Down, TrackType::Intersection)      if self.current_turns % 3 == 0  => CartDirection::Right,
             (CartDirection::Down, TrackType::Intersection)      if self.current_turns % 3 == 1  => CartDirection::Down,
             (CartDirection::Down, TrackType::Intersection)      if self.current_turns % 3 == 2  => CartDirection::Left,
             
-            (CartDirection::Left, TrackType::StraightUpDown)                                    => panic!("🚨 Cart movement is illegal at '({}, {})'!", position.get_x(), position.get_y()),
+            (CartDirection::Left, TrackType::StraightUpDown)                                    => return Err(Day13Error::IllegalMovement(position)),
             (CartDirection::Left, TrackType::StraightLeftRight)                                 => CartDirection::Left,
             (CartDirection::Left, TrackType::DiagonalUpDown)                                    => CartDirection::Up,
             (CartDirection::Left, TrackType::DiagonalDownUp)                                    => CartDirection::Down,
@@ -152,7 +177,7 @@ impl Cart {
             (CartDirection::Left, TrackType::Intersection)      if self.current_turns % 3 == 1  => CartDirection::Left,
             (CartDirection::Left, TrackType::Intersection)      if self.current_turns % 3 == 2  => CartDirection::Up,
             
-            (CartDirection::Right, TrackType::StraightUpDown)                                   => panic!("🚨 Cart movement is illegal at '({}, {})'!", position.get_x(), position.get_y()),
+            (CartDirection::Right, TrackType::StraightUpDown)                                   => return Err(Day13Error::IllegalMovement(position)),
             (CartDirection::Right, TrackType::StraightLeftRight)                                => CartDirection::Right,
             (CartDirection::Right, TrackType::DiagonalUpDown)                                   => CartDirection::Down,
             (CartDirection::Right, TrackType::DiagonalDownUp)                                   => CartDirection::Up,
@@ -160,19 +185,23 @@ impl Cart {
             (CartDirection::Right, TrackType::Intersection)     if self.current_turns % 3 == 1  => CartDirection::Right,
             (CartDirection::Right, TrackType::Intersection)     if self.current_turns % 3 == 2  => CartDirection::Down,
             
-            (_, TrackType::Intersection)                                                        => panic!("🚨 Never will be called!"),
+            (_, TrackType::Intersection)                                                        => unreachable!("turns are always taken modulo 3"),
         };
 
         if matches!(position_track_type, TrackType::Intersection) {
             self.current_turns = self.current_turns + 1;
         }
+
+        return Ok(());
     }
 }
 
 impl Simulator {
-    pub fn  new(initial_map: Vec<Vec<char>>) -> Simulator {
+    pub fn  new(initial_map: Vec<Vec<char>>) -> Result<Simulator, Day13Error> {
 
-        let mut map_track : HashMap<Coordinate2D, Track> = HashMap::new();
+        let map_width : usize = initial_map.iter().map(|line| line.len()).max().unwrap_or(0);
+        let map_limits : BoundingBox<CoordinateUnit> = BoundingBox::new(Coordinate2D::new(0, 0), Coordinate2D::new(map_width as CoordinateUnit - 1, initial_map.len() as CoordinateUnit - 1));
+        let mut map_track : Grid<Option<Track>> = Grid::new(map_limits, None);
         let mut carts : HashMap<Coordinate2D, Cart> = HashMap::new();
 
         for (index_line, line) in initial_map.into_iter().enumerate() {
@@ -183,7 +212,7 @@ impl Simulator {
                 let cart_direction_option : Option<CartDirection> = _convert_char_cart_direction(&char);
 
                 match (track_type_option, cart_direction_option) {
-                    (Some(track_type),  _                   ) => { map_track.insert(position, Track::new(track_type)); },
+                    (Some(track_type),  _                   ) => { map_track[position] = Some(Track::new(track_type)); },
                     (_,                 Some(cart_direction)) => { carts.insert(position, Cart::new(carts.len(), position, cart_direction)); },
                     (_,                 _                   ) => (),
                 };
@@ -191,10 +220,10 @@ impl Simulator {
         }
 
         for (&position, _) in carts.iter() {
-            let above_track_option  = map_track.get(&(position - Coordinate2D::new( 0, -1)));
-            let under_track_option  = map_track.get(&(position - Coordinate2D::new( 0,  1)));
-            let left_track_option   = map_track.get(&(position - Coordinate2D::new(-1,  0)));
-            let right_track_option  = map_track.get(&(position - Coordinate2D::new( 1,  0)));
+            let above_track_option  = get_track(&map_track, position - Coordinate2D::new( 0, -1));
+            let under_track_option  = get_track(&map_track, position - Coordinate2D::new( 0,  1));
+            let left_track_option   = get_track(&map_track, position - Coordinate2D::new(-1,  0));
+            let right_track_option  = get_track(&map_track, position - Coordinate2D::new( 1,  0));
 
             let opt_above = above_track_option.is_some() && above_track_option.unwrap().connectable_opt_under();
             let opt_under = under_track_option.is_some() && under_track_option.unwrap().connectable_opt_above();
@@ -218,20 +247,20 @@ impl Simulator {
                 (    _,     _,     _,     _,  true,  true, false, false) => TrackType::StraightUpDown,
                 (    _,     _,     _,     _,  false, false, true,  true) => TrackType::StraightLeftRight,
 
-                _ => panic!("🚨 Not recognized as a valid surrounding for track in '({}, {})'", position.get_x(), position.get_y()),
+                _ => return Err(Day13Error::UnrecognizedSurrounding(position)),
             };
 
-            map_track.insert(position, Track::new(current_position_track_type));
+            map_track[position] = Some(Track::new(current_position_track_type));
         }
 
-        return Simulator { iteration: 0, map: map_track, carts: carts, collisions: HashMap::new() }
+        return Ok(Simulator { iteration: 0, map: map_track, carts: carts, collisions: HashMap::new() });
     }
 
     pub fn get_iteration(&self) -> usize { self.iteration }
     pub fn get_crashes(&self) -> Vec<(&Coordinate2D, &Vec<usize>)> { self.collisions.iter().collect() }
     pub fn get_carts_positions(&self) -> Vec<&Coordinate2D> { self.carts.iter().map(|(position, _)| position).collect() }
 
-    pub fn run_iteration(&mut self) {
+    pub fn run_iteration(&mut self) -> Result<(), Day13Error> {
 
         let mut new_carts : HashMap<Coordinate2D, Cart> = HashMap::new();
         let mut carts_pos_to_iter : Vec<Coordinate2D> = self.carts.iter()
@@ -245,16 +274,12 @@ impl Simulator {
 
             let mut cart : Cart = cart_option.unwrap();
             let next_position_cart : Coordinate2D = cart.get_next_position();
-            let track_in_next_position_cart : &Track = self.map.get(&next_position_cart).unwrap();
+            let track_in_next_position_cart : &Track = get_track(&self.map, next_position_cart).ok_or(Day13Error::OffTrack(next_position_cart))?;
 
-            cart.move_cart(next_position_cart, track_in_next_position_cart.track_type);
+            cart.move_cart(next_position_cart, track_in_next_position_cart.track_type)?;
 
             // Check for collisions and deal with them correctly
-            if false && self.collisions.contains_key(&next_position_cart) {
-                // Not suposed to work like this
-                let current_collisions = self.collisions.get_mut(&next_position_cart).unwrap();
-                current_collisions.push(cart.id);
-            } else if new_carts.contains_key(&next_position_cart) {
+            if new_carts.contains_key(&next_position_cart) {
                 let other_car = new_carts.remove(&next_position_cart).unwrap();
                 if self.collisions.contains_key(&next_position_cart) {
                     let current_collisions = self.collisions.get_mut(&next_position_cart).unwrap();
@@ -273,44 +298,156 @@ impl Simulator {
 
         self.iteration = self.iteration + 1;
         self.carts = new_carts;
-    }
-
-    fn _get_limits_map(&self) -> (Coordinate2D, Coordinate2D) {
-        let positions_map = self.map.iter().map(|(position, _)| position).collect::<Vec<&Coordinate2D>>();
-        let positions_cart = self.carts.iter().map(|(position, _)| position).collect::<Vec<&Coordinate2D>>();
-        let positions_crashes = self.collisions.iter().map(|(position, _)| position).collect::<Vec<&Coordinate2D>>();
 
-        let final_iterator = positions_map.iter().chain(positions_cart.iter()).chain(positions_crashes.iter());
-        let min_position_x = final_iterator.clone().map(|position| position.get_x()).min().unwrap();
-        let min_position_y = final_iterator.clone().map(|position| position.get_y()).min().unwrap();
-        let max_position_x = final_iterator.clone().map(|position| position.get_x()).max().unwrap();
-        let max_position_y = final_iterator.clone().map(|position| position.get_y()).max().unwrap();
+        debug!(iteration = self.iteration, carts = self.carts.len(), crashes = self.collisions.len(), "carts moved");
+        trace!("{}", self.print_map(true));
+        return Ok(());
+    }
 
-        return (Coordinate2D::new(min_position_x, min_position_y), Coordinate2D::new(max_position_x, max_position_y));
+    fn _get_limits_map(&self) -> BoundingBox<CoordinateUnit> {
+        return self.map.get_bounds();
     }
 
-    pub fn _print_map(&self, print_collisions: bool) -> String {
+    pub fn print_map(&self, print_collisions: bool) -> String {
 
         let mut final_string : String = format!("🛒 Map on iteration '{}'\n", self.iteration);
         let limits = self._get_limits_map();
-        for position_y in limits.0.get_y()..=limits.1.get_y() {
-            for position_x in limits.0.get_x()..=limits.1.get_x() {
-                let check_position : Coordinate2D = Coordinate2D::new(position_x, position_y);
-                let track_option : Option<&Track> = self.map.get(&check_position);
-                let cart_option : Option<&Cart> = self.carts.get(&check_position);
-                let collision_place : bool = self.collisions.contains_key(&check_position);
-
-                match (track_option, cart_option, collision_place) {
-                    (_,             _,          true) if print_collisions   => final_string.push('X'),
-                    (_,             Some(cart), _   )                       => final_string.push(_convert_cart_direction_char(&cart.cart_direction)),
-                    (Some(track),   None,       _   )                       => final_string.push(_convert_track_type_char(&track.track_type)),
-                    (None,          None,       _   )                       => final_string.push(' '),
-                }
+        for check_position in limits.points() {
+            let track_option : Option<&Track> = get_track(&self.map, check_position);
+            let cart_option : Option<&Cart> = self.carts.get(&check_position);
+            let collision_place : bool = self.collisions.contains_key(&check_position);
+
+            match (track_option, cart_option, collision_place) {
+                (_,             _,          true) if print_collisions   => final_string.push('X'),
+                (_,             Some(cart), _   )                       => final_string.push(_convert_cart_direction_char(&cart.cart_direction)),
+                (Some(track),   None,       _   )                       => final_string.push(_convert_track_type_char(&track.track_type)),
+                (None,          None,       _   )                       => final_string.push(' '),
             }
 
-            final_string.push('\n');
+            if check_position.x == limits.max.x { final_string.push('\n'); }
         }
 
         return final_string;
     }
+
+    /// Frame of the current iteration: tracks in gray, every cart in its own color and the crash sites in red.
+    pub fn render_frame(&self) -> Image {
+        return Image::from_grid(&self.map, |position, track_option| {
+            if self.collisions.contains_key(&position) { return CRASH_COLOR; }
+            if let Some(cart) = self.carts.get(&position) { return palette_color(cart.id); }
+            match track_option {
+                Some(Track { track_type: TrackType::Intersection }) => INTERSECTION_COLOR,
+                Some(_) => TRACK_COLOR,
+                None => BLACK,
+            }
+        }).scale(RENDER_SCALE);
+    }
+}
+
+// ============================================================= EXAMPLES ==============================================================
+
+const FIRST_EXAMPLE : &str = r"/->-\
+|   |  /----\
+| /-+--+-\  |
+| | |  | v  |
+\-+-/  \-+--/
+  \------/";
+
+const SECOND_EXAMPLE : &str = r"/>-<\
+|   |
+| /<+-\
+| | | v
+\>+</ |
+  |   ^
+  \<->/";
+
+// ====================================================== SOLUTION IMPLEMENTATION ======================================================
+
+impl Solution for Day13 {
+    const DAY : DayNumber = 13;
+    const TITLE : &'static str = "Mine Cart Madness";
+
+    type Input = Simulator;
+    type Part1 = CartPosition;
+    type Part2 = CartPosition;
+    type Error = Day13Error;
+
+    fn parse(&self, input: &Input) -> Result<Simulator, Day13Error> {
+        return Simulator::new(input.chars());
+    }
+
+    fn part1(&self, simulator: &Simulator) -> Result<CartPosition, Day13Error> {
+        let mut simulator : Simulator = simulator.clone();
+        while simulator.get_crashes().len() == 0 {
+            if simulator.get_carts_positions().len() < 2 { return Err(Day13Error::NotEnoughCarts); }
+            simulator.run_iteration()?;
+        }
+
+        let crash_position : Coordinate2D = *simulator.get_crashes().get(0).unwrap().0;
+        return Ok(CartPosition { position: crash_position, iteration: simulator.get_iteration() });
+    }
+
+    fn part2(&self, simulator: &Simulator) -> Result<CartPosition, Day13Error> {
+        let mut simulator : Simulator = simulator.clone();
+        while simulator.get_carts_positions().len() > 1 { simulator.run_iteration()?; }
+
+        let cart_position : Coordinate2D = **simulator.get_carts_positions().get(0).ok_or(Day13Error::NoCartSurvived)?;
+        return Ok(CartPosition { position: cart_position, iteration: simulator.get_iteration() });
+    }
+
+    fn render(&self, simulator: &Simulator) -> Result<Vec<Image>, Day13Error> {
+        let mut simulator : Simulator = simulator.clone();
+        let mut frames : Vec<Image> = vec![simulator.render_frame()];
+        while simulator.get_crashes().is_empty() && simulator.get_carts_positions().len() >= 2 {
+            simulator.run_iteration()?;
+            frames.push(simulator.render_frame());
+        }
+
+        return Ok(frames);
+    }
+
+    fn examples() -> Vec<Example<Day13>> {
+        return vec![
+            Example::new(FIRST_EXAMPLE, Day13).part1("7,3"),
+            Example::new(SECOND_EXAMPLE, Day13).part2("6,4"),
+        ];
+    }
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn finds_location_of_first_crash() {
+        let simulator : Simulator = Day13.parse(&Input::from_string("example", FIRST_EXAMPLE)).unwrap();
+        let crash : CartPosition = Day13.part1(&simulator).unwrap();
+        assert_eq!((crash.to_string(), crash.iteration), ("7,3".to_owned(), 14));
+    }
+
+    #[test]
+    fn finds_location_of_last_cart() {
+        let simulator : Simulator = Day13.parse(&Input::from_string("example", SECOND_EXAMPLE)).unwrap();
+        assert_eq!(Day13.part2(&simulator).unwrap().to_string(), "6,4");
+    }
+
+    #[test]
+    fn rejects_carts_going_off_track() {
+        let mut simulator : Simulator = Day13.parse(&Input::from_string("example", "->--")).unwrap();
+        assert!(matches!(simulator.run_iteration(), Ok(())));
+        assert!(matches!(simulator.run_iteration(), Ok(())));
+        assert!(matches!(simulator.run_iteration(), Err(Day13Error::OffTrack(_))));
+    }
+
+    #[test]
+    fn renders_frames_until_first_crash() {
+        let simulator : Simulator = Day13.parse(&Input::from_string("example", FIRST_EXAMPLE)).unwrap();
+        let frames : Vec<Image> = Day13.render(&simulator).unwrap();
+        assert_eq!(frames.len(), 15);
+        assert_eq!((frames[0].get_width(), frames[0].get_height()), (13 * RENDER_SCALE, 6 * RENDER_SCALE));
+        assert_eq!(frames[14].get_pixel(7 * RENDER_SCALE, 3 * RENDER_SCALE), Some(CRASH_COLOR));
+    }
 }
\ No newline at end of file
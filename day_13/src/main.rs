@@ -1,33 +1,37 @@
-mod read;
-mod lib;
+use common::{CountingAllocator, OutputFormat, Solution, check_example, example_from_args, init_logging, print_json_answers, print_solution_stats, render_path_from_args, save_render, stats_from_args, verbosity_from_args};
+use read::Input;
+use day_13::Day13;
 
-use lib::{Coordinate2D, Simulator};
+#[global_allocator]
+static ALLOCATOR : CountingAllocator = CountingAllocator;
 
 fn main() {
 
-    let input_read : Vec<Vec<char>> = read::read_chars("input.txt".to_owned());
-    let mut simulator : Simulator = Simulator::new(input_read);
+    init_logging(verbosity_from_args());
+    if let Some(example) = example_from_args().unwrap() {
+        std::process::exit(if check_example::<Day13>(example).unwrap() { 0 } else { 1 });
+    }
 
-    // Part 1
-    //println!("{}\n", simulator._print_map(true));
-    while simulator.get_crashes().len() == 0 {
-        simulator.run_iteration();
-        //println!("{}\n", simulator._print_map(true));
+    let input = Input::from_path("input.txt").unwrap();
+    if let Some(render_path) = render_path_from_args().unwrap() {
+        for frame_path in save_render(&Day13, &input, &render_path).unwrap() {
+            eprintln!("🖼️  Rendered '{}'", frame_path.display());
+        }
+    }
+    if OutputFormat::from_args().unwrap() == OutputFormat::Json {
+        return print_json_answers(&Day13, &input).unwrap();
+    }
+    if stats_from_args() {
+        return print_solution_stats(&Day13, &input).unwrap();
     }
-    let crashes : Vec<(&Coordinate2D, &Vec<usize>)> = simulator.get_crashes();
-    let iteration : usize = simulator.get_iteration();
-    let crash : &(&Coordinate2D, &Vec<usize>) = crashes.get(0).unwrap();
-    println!("🛒 The first crash was registered after '{}' iterations, at '({}, {})' (Part 1)", iteration, crash.0.get_x(), crash.0.get_y());
+
+    let simulator = Day13.parse(&input).unwrap();
+
+    // Part 1
+    let crash = Day13.part1(&simulator).unwrap();
+    println!("🛒 The first crash was registered after '{}' iterations, at '({}, {})' (Part 1)", crash.iteration, crash.position.get_x(), crash.position.get_y());
 
     // Part 2
-    //println!("{}\n", simulator._print_map(false));
-    while simulator.get_carts_positions().len() != 1 {
-        simulator.run_iteration();
-        //println!("{}\n", simulator._print_map(false));
-    }
-    let remaining_carts : Vec<&Coordinate2D> = simulator.get_carts_positions();
-    let iteration : usize = simulator.get_iteration();
-    let cart_position : &Coordinate2D = remaining_carts.get(0).unwrap();
-    println!("🛒 The last cart is at '({}, {})' after '{}' iterations (Part 2)", cart_position.get_x(), cart_position.get_y(), iteration);
-    
+    let last_cart = Day13.part2(&simulator).unwrap();
+    println!("🛒 The last cart is at '({}, {})' after '{}' iterations (Part 2)", last_cart.position.get_x(), last_cart.position.get_y(), last_cart.iteration);
 }
\ No newline at end of file
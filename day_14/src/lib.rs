@@ -1,12 +1,49 @@
 
 
+use std::error::Error;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, trace};
+
+use common::{DayNumber, Example, Solution};
+use read::{Input, ReadError};
+
+// ======================================================= CONSTANTS DEFINITIONS =======================================================
+
+pub const NUMBER_OF_ELVES : usize = 2;
+pub const SIZE_RECIPE_IMPROVEMENT : usize = 10;
+pub const ORIGINAL_RECIPES : [RecipeValue; 2] = [3, 7];
+
 // ======================================================== STRUCTS DEFINITIONS ========================================================
 
-type RecipeValue = i64;
+pub type RecipeValue = i64;
 
 #[derive(Clone, Copy)]
 pub struct Recipe { value: RecipeValue }
-pub struct RecipeManager { itearation: usize, elves: Vec<usize>, recipes: Vec<Recipe>, improvement_size: usize }
+pub struct RecipeManager { itearation: usize, elves: Vec<usize>, recipes: Vec<Recipe>, new_recipes: usize, improvement_size: usize }
+
+#[derive(Serialize)]
+pub struct RecipeEstimation { pub scores: String, pub iteration: usize }
+#[derive(Serialize)]
+pub struct RecipeMatch { pub recipes_to_the_left: usize, pub iteration: usize }
+
+#[derive(Debug)]
+pub enum Day14Error {
+    Read(ReadError),
+    InvalidNumber(String),
+    NoElves,
+    NoRecipes,
+    InvalidRecipe(RecipeValue),
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Day14 {
+    pub number_of_elves: usize,
+    pub original_recipes: Vec<RecipeValue>,
+    pub improvement_size: usize,
+}
 
 // ======================================================== AUXILIARY FUNCTIONS ========================================================
 
@@ -14,6 +51,18 @@ pub struct RecipeManager { itearation: usize, elves: Vec<usize>, recipes: Vec<Re
 
 // ====================================================== STRUCTS IMPLEMENTATIONS ======================================================
 
+impl fmt::Display for RecipeEstimation {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}", self.scores)
+    }
+}
+
+impl fmt::Display for RecipeMatch {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}", self.recipes_to_the_left)
+    }
+}
+
 impl Recipe {
     pub fn new(recipe_value: RecipeValue) -> Recipe {
         Recipe { value: recipe_value }
@@ -22,14 +71,46 @@ impl Recipe {
     fn get_value(&self) -> RecipeValue { self.value }
 }
 
+impl fmt::Display for Day14Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day14Error::Read(error) => write!(formatter, "{}", error),
+            Day14Error::InvalidNumber(number) => write!(formatter, "puzzle input '{}' is not a valid number", number),
+            Day14Error::NoElves => write!(formatter, "at least one elf is needed to create recipes"),
+            Day14Error::NoRecipes => write!(formatter, "at least one original recipe is needed"),
+            Day14Error::InvalidRecipe(recipe_value) => write!(formatter, "recipe score '{}' is not a single digit", recipe_value),
+        }
+    }
+}
+
+impl Error for Day14Error {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Day14Error::Read(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ReadError> for Day14Error {
+    fn from(error: ReadError) -> Day14Error { Day14Error::Read(error) }
+}
+
 impl RecipeManager {
-    pub fn new(number_elves: usize, recipes: Vec<Recipe>, improvement_size: usize) -> RecipeManager {
-        RecipeManager {
+    pub fn new(number_elves: usize, recipes: Vec<Recipe>, improvement_size: usize) -> Result<RecipeManager, Day14Error> {
+        if number_elves == 0 { return Err(Day14Error::NoElves); }
+        if recipes.is_empty() { return Err(Day14Error::NoRecipes); }
+        if let Some(recipe) = recipes.iter().find(|recipe| !(0..=9).contains(&recipe.get_value())) {
+            return Err(Day14Error::InvalidRecipe(recipe.get_value()));
+        }
+
+        Ok(RecipeManager {
             itearation: 0,
             elves: (0..number_elves).into_iter().collect(),
+            new_recipes: recipes.len(),
             recipes: recipes,
             improvement_size: improvement_size,
-        }
+        })
     }
 
     pub fn get_iteration(&self) -> usize { self.itearation }
@@ -44,30 +125,16 @@ impl RecipeManager {
             .join(""));
     }
 
+    /// Recipes to the left of the scores, when they end with one of the recipes created by the last iteration (or with one of the
+    /// original recipes before the first one), the earliest ending first.
     pub fn compare_last_recipe_match(&self, recipe_value: &str) -> Option<usize> {
-        let recipe_len = recipe_value.len();
-
-        if self.recipes.len() < recipe_len { return None; }
-        let final_recipe_first : String = (0..recipe_len).into_iter()
-            .map(|index| self.recipes.len() - 1 - index)
-            .map(|index| self.recipes.get(index).unwrap())
-            .map(|recipe| recipe.get_value().to_string())
-            .rev()
-            .collect::<Vec<String>>()
-            .join("");
-        if &final_recipe_first == recipe_value { return Some(self.recipes.len() - recipe_len); }
-        
-        if self.recipes.len() < recipe_len + 1 { return None; }
-        let final_recipe_second : String = (1..(recipe_len + 1)).into_iter()
-            .map(|index| self.recipes.len() - 1 - index)
-            .map(|index| self.recipes.get(index).unwrap())
-            .map(|recipe| recipe.get_value().to_string())
-            .rev()
-            .collect::<Vec<String>>()
-            .join("");
-        if &final_recipe_second == recipe_value { return Some(self.recipes.len() - recipe_len - 1); }
+        let recipe_len : usize = recipe_value.len();
+        let first_end : usize = std::cmp::max(self.recipes.len() + 1 - self.new_recipes, recipe_len);
 
-        return None;
+        return (first_end..=self.recipes.len())
+            .map(|end| end - recipe_len)
+            .find(|&start| self.recipes[start..start + recipe_len].iter().zip(recipe_value.chars())
+                .all(|(recipe, digit)| digit.to_digit(10) == Some(recipe.get_value() as u32)));
     }
 
     pub fn run_iteration(&mut self) {
@@ -88,28 +155,190 @@ impl RecipeManager {
             .rev()
             .collect();
 
+        self.new_recipes = new_values.len();
         for new_value in new_values.into_iter() { self.recipes.push(Recipe::new(new_value)); }
         let current_number_recipes : usize = self.recipes.len();
         self.elves = self.elves.iter().zip(elves_values.into_iter())
             .map(|(&elf_index, elf_value)| (elf_index + 1 + elf_value as usize) % current_number_recipes)
             .collect();
-    }
 
-    pub fn _print_formatted(&self) -> String {
+        debug!(iteration = self.itearation, recipes = self.recipes.len(), elves = ?self.elves, "recipes created");
+        trace!("{}", self.print_formatted());
+    }
 
-        let elf_1_index : usize = *self.elves.get(0).unwrap();
-        let elf_2_index : usize = *self.elves.get(1).unwrap();
+    /// Recipes of the scoreboard, the one of the first elf in parentheses, of the second in brackets and of any other in braces.
+    pub fn print_formatted(&self) -> String {
 
         let mut final_string : String = format!("☕ On iteration '{}': ", self.itearation);
         let recipe_values_string : String = self.recipes.iter().enumerate()
-            .map(|(index, recipe)| {
-                if index == elf_1_index { format!("({})", recipe.get_value()) }
-                else if index == elf_2_index { format!("[{}]", recipe.get_value()) }
-                else { format!(" {} ", recipe.get_value()) }})
+            .map(|(index, recipe)| match self.elves.iter().position(|&elf_index| elf_index == index) {
+                Some(0) => format!("({})", recipe.get_value()),
+                Some(1) => format!("[{}]", recipe.get_value()),
+                Some(_) => format!("{{{}}}", recipe.get_value()),
+                None => format!(" {} ", recipe.get_value()),
+            })
             .collect::<Vec<String>>()
             .join(" ");
 
         final_string.push_str(&recipe_values_string);
         return final_string;
     }
+}
+
+// ====================================================== SOLUTION IMPLEMENTATION ======================================================
+
+impl Day14 {
+    fn create_recipe_manager(&self) -> Result<RecipeManager, Day14Error> {
+        let recipes : Vec<Recipe> = self.original_recipes.iter()
+            .map(|&recipe_value| Recipe::new(recipe_value))
+            .collect();
+        return RecipeManager::new(self.number_of_elves, recipes, self.improvement_size);
+    }
+}
+
+impl Default for Day14 {
+    fn default() -> Day14 {
+        Day14 { number_of_elves: NUMBER_OF_ELVES, original_recipes: ORIGINAL_RECIPES.to_vec(), improvement_size: SIZE_RECIPE_IMPROVEMENT }
+    }
+}
+
+impl Solution for Day14 {
+    const DAY : DayNumber = 14;
+    const TITLE : &'static str = "Chocolate Charts";
+
+    type Input = String;
+    type Part1 = RecipeEstimation;
+    type Part2 = RecipeMatch;
+    type Error = Day14Error;
+
+    fn parse(&self, input: &Input) -> Result<String, Day14Error> {
+        let input_number : &str = input.first_line()?.trim();
+        if input_number.is_empty() || !input_number.chars().all(|digit| digit.is_ascii_digit()) {
+            return Err(Day14Error::InvalidNumber(input_number.to_owned()));
+        }
+
+        return Ok(input_number.to_owned());
+    }
+
+    fn part1(&self, input_number: &String) -> Result<RecipeEstimation, Day14Error> {
+        let after_value : usize = input_number.parse().map_err(|_| Day14Error::InvalidNumber(input_number.clone()))?;
+        let mut recipe_manager : RecipeManager = self.create_recipe_manager()?;
+        let mut final_recipe_estimation : Option<String> = None;
+        while final_recipe_estimation.is_none() {
+            recipe_manager.run_iteration();
+            final_recipe_estimation = recipe_manager.estimate_improvement(after_value);
+        }
+
+        return Ok(RecipeEstimation { scores: final_recipe_estimation.unwrap(), iteration: recipe_manager.get_iteration() });
+    }
+
+    fn part2(&self, input_number: &String) -> Result<RecipeMatch, Day14Error> {
+        let mut recipe_manager : RecipeManager = self.create_recipe_manager()?;
+        let mut scores_to_the_left : Option<usize> = recipe_manager.compare_last_recipe_match(input_number);
+        while scores_to_the_left.is_none() {
+            recipe_manager.run_iteration();
+            scores_to_the_left = recipe_manager.compare_last_recipe_match(input_number);
+        }
+
+        return Ok(RecipeMatch { recipes_to_the_left: scores_to_the_left.unwrap(), iteration: recipe_manager.get_iteration() });
+    }
+
+    fn examples() -> Vec<Example<Day14>> {
+        return vec![
+            Example::new("9", Day14::default()).part1("5158916779"),
+            Example::new("5", Day14::default()).part1("0124515891"),
+            Example::new("18", Day14::default()).part1("9251071085"),
+            Example::new("2018", Day14::default()).part1("5941429882"),
+            Example::new("51589", Day14::default()).part2("9"),
+            Example::new("01245", Day14::default()).part2("5"),
+            Example::new("92510", Day14::default()).part2("18"),
+            Example::new("59414", Day14::default()).part2("2018"),
+        ];
+    }
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn estimates_scores_after_recipes() {
+        let estimations : [(&str, &str); 4] = [("9", "5158916779"), ("5", "0124515891"), ("18", "9251071085"), ("2018", "5941429882")];
+        for (after_value, scores) in estimations.into_iter() {
+            assert_eq!(Day14::default().part1(&after_value.to_owned()).unwrap().scores, scores);
+        }
+    }
+
+    #[test]
+    fn counts_recipes_before_scores() {
+        let matches : [(&str, usize); 4] = [("51589", 9), ("01245", 5), ("92510", 18), ("59414", 2018)];
+        for (scores, recipes_to_the_left) in matches.into_iter() {
+            assert_eq!(Day14::default().part2(&scores.to_owned()).unwrap().recipes_to_the_left, recipes_to_the_left);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn suffix_check_finds_first_occurrence(number_elves in 1..20_usize, original_recipes in prop::collection::vec(0..10_i64, 1..5),
+            scores in "[0-9]{1,4}") {
+
+            let recipes : Vec<Recipe> = original_recipes.into_iter().map(|recipe_value| Recipe::new(recipe_value)).collect();
+            let mut recipe_manager : RecipeManager = RecipeManager::new(number_elves, recipes, SIZE_RECIPE_IMPROVEMENT).unwrap();
+            let mut scores_to_the_left : Option<usize> = recipe_manager.compare_last_recipe_match(&scores);
+            while scores_to_the_left.is_none() && recipe_manager.get_iteration() < 5_000 {
+                recipe_manager.run_iteration();
+                scores_to_the_left = recipe_manager.compare_last_recipe_match(&scores);
+            }
+
+            let all_scores : String = recipe_manager.recipes.iter().map(|recipe| recipe.get_value().to_string()).collect();
+            prop_assert_eq!(scores_to_the_left, all_scores.find(scores.as_str()));
+        }
+    }
+
+    #[test]
+    fn finds_scores_among_original_recipes() {
+        assert_eq!(Day14::default().part2(&"37".to_owned()).unwrap().recipes_to_the_left, 0);
+        assert_eq!(Day14::default().part2(&"7".to_owned()).unwrap().recipes_to_the_left, 1);
+    }
+
+    #[test]
+    fn finds_scores_ending_on_any_new_recipe() {
+        // Twelve elves on nines create three recipes (1, 0 and 8) on the first iteration, the scores ending on the first of them
+        let day : Day14 = Day14 { number_of_elves: 12, original_recipes: vec![9; 12], ..Day14::default() };
+        let recipe_match : RecipeMatch = day.part2(&"991".to_owned()).unwrap();
+        assert_eq!((recipe_match.recipes_to_the_left, recipe_match.iteration), (10, 1));
+    }
+
+    #[test]
+    fn formats_recipes_with_a_single_elf() {
+        let recipes : Vec<Recipe> = ORIGINAL_RECIPES.iter().map(|&recipe_value| Recipe::new(recipe_value)).collect();
+        let recipe_manager : RecipeManager = RecipeManager::new(1, recipes, SIZE_RECIPE_IMPROVEMENT).unwrap();
+        assert_eq!(recipe_manager.print_formatted(), "☕ On iteration '0': (3)  7 ");
+    }
+
+    #[test]
+    fn formats_recipes_of_every_elf() {
+        let recipes : Vec<Recipe> = [3, 7, 1, 0].iter().map(|&recipe_value| Recipe::new(recipe_value)).collect();
+        let mut recipe_manager : RecipeManager = RecipeManager::new(3, recipes, SIZE_RECIPE_IMPROVEMENT).unwrap();
+        assert_eq!(recipe_manager.print_formatted(), "☕ On iteration '0': (3) [7] {1}  0 ");
+
+        recipe_manager.run_iteration();
+        assert_eq!(recipe_manager.print_formatted(), "☕ On iteration '1':  3   7   1  [0] (1)  1 ");
+        assert_eq!(Day14 { number_of_elves: 3, ..Day14::default() }.part1(&"5".to_owned()).unwrap().scores.len(), SIZE_RECIPE_IMPROVEMENT);
+    }
+
+    #[test]
+    fn rejects_missing_elves() {
+        assert!(matches!(Day14 { number_of_elves: 0, ..Day14::default() }.part1(&"5".to_owned()), Err(Day14Error::NoElves)));
+        assert!(matches!(Day14 { number_of_elves: 0, ..Day14::default() }.part2(&"51589".to_owned()), Err(Day14Error::NoElves)));
+    }
+
+    #[test]
+    fn keeps_leading_zeros_of_input() {
+        assert_eq!(Day14::default().parse(&Input::from_string("example", "01245\n")).unwrap(), "01245");
+        assert!(matches!(Day14::default().parse(&Input::from_string("example", "12a")), Err(Day14Error::InvalidNumber(_))));
+    }
 }
\ No newline at end of file
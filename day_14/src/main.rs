@@ -1,45 +1,33 @@
-mod read;
-mod lib;
+use common::{CountingAllocator, OutputFormat, Solution, check_example, example_from_args, init_logging, print_json_answers, print_solution_stats, solution_from_args, stats_from_args, verbosity_from_args};
+use read::Input;
+use day_14::Day14;
 
-use lib::{Recipe, RecipeManager};
+#[global_allocator]
+static ALLOCATOR : CountingAllocator = CountingAllocator;
 
 fn main() {
 
-    let lines_read : Vec<i64> = read::read_int_lines("input.txt".to_owned());
-    let input_number : usize = *lines_read.get(0).unwrap() as usize;
-    let input_number_string : String = input_number.to_string();
+    init_logging(verbosity_from_args());
+    if let Some(example) = example_from_args().unwrap() {
+        std::process::exit(if check_example::<Day14>(example).unwrap() { 0 } else { 1 });
+    }
 
-    // Constants definition
-    let number_of_elfes : usize = 2;
-    let size_recipe_improvement : usize = 10;
-    let original_recipes : Vec<i64> = vec![3, 7];
+    let input = Input::from_path("input.txt").unwrap();
+    let solution : Day14 = solution_from_args().unwrap();
+    if OutputFormat::from_args().unwrap() == OutputFormat::Json {
+        return print_json_answers(&solution, &input).unwrap();
+    }
+    if stats_from_args() {
+        return print_solution_stats(&solution, &input).unwrap();
+    }
 
-    // Create recipes and recipe manager
-    let recipes : Vec<Recipe> = original_recipes.into_iter()
-        .map(|recipe_value| Recipe::new(recipe_value))
-        .collect();
+    let input_number = solution.parse(&input).unwrap();
         
     // Part 1
-    let mut recipe_manager = RecipeManager::new(number_of_elfes, recipes.clone(), size_recipe_improvement);
-    let mut final_recipe_estimation : Option<String> = None;
-    //println!("{}", recipe_manager._print_formatted());
-    while final_recipe_estimation.is_none() {
-        recipe_manager.run_iteration();
-        //println!("{}", recipe_manager._print_formatted());
-        final_recipe_estimation = recipe_manager.estimate_improvement(input_number);
-    }
-    let current_iteration : usize = recipe_manager.get_iteration();
-    println!("☕ After '{}' iterations, the final recipe estimated immediately after '{}' is '{}' (Part 1)", current_iteration, input_number, final_recipe_estimation.unwrap());
+    let final_recipe_estimation = solution.part1(&input_number).unwrap();
+    println!("☕ After '{}' iterations, the final recipe estimated immediately after '{}' is '{}' (Part 1)", final_recipe_estimation.iteration, input_number, final_recipe_estimation.scores);
     
     // Part 2
-    let mut recipe_manager = RecipeManager::new(number_of_elfes, recipes, size_recipe_improvement);
-    let mut scores_to_the_left : Option<usize> = None;
-    //println!("{}", recipe_manager._print_formatted());
-    while scores_to_the_left.is_none() {
-        recipe_manager.run_iteration();
-        //println!("{}", recipe_manager._print_formatted());
-        scores_to_the_left = recipe_manager.compare_last_recipe_match(&input_number_string);
-    }
-    let current_iteration : usize = recipe_manager.get_iteration();
-    println!("☕ After '{}' iterations, the recipe is matched '{}' with '{}' scores to the left (Part 1)", current_iteration, input_number_string, scores_to_the_left.unwrap());
+    let recipe_match = solution.part2(&input_number).unwrap();
+    println!("☕ After '{}' iterations, the recipe is matched '{}' with '{}' scores to the left (Part 2)", recipe_match.iteration, input_number, recipe_match.recipes_to_the_left);
 }
\ No newline at end of file
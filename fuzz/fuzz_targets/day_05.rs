@@ -0,0 +1,7 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use common::fuzz_parse;
+
+fuzz_target!(|data: &[u8]| fuzz_parse::<day_05::Day05>(data));
\ No newline at end of file
@@ -0,0 +1,383 @@
+use std::collections::HashSet;
+
+use crate::rng::SyntheticRng;
+
+// ======================================================= CONSTANTS DEFINITIONS =======================================================
+
+const MONTH_LENGTHS : [usize; 12] = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+
+const GLYPH_WIDTH : usize = 4;
+const GLYPH_HEIGHT : usize = 6;
+const GLYPHS : [(char, [&str; GLYPH_HEIGHT]); 14] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+const CELLS_PER_ROW : usize = 8;
+
+// ======================================================== AUXILIARY FUNCTIONS ========================================================
+
+fn is_leap_year(year: usize) -> bool {
+    return (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+}
+
+fn next_date((year, month, day): (usize, usize, usize)) -> (usize, usize, usize) {
+    let month_length : usize = if month == 2 && is_leap_year(year) { 29 } else { MONTH_LENGTHS[month - 1] };
+    if day < month_length { return (year, month, day + 1); }
+    if month < 12 { return (year, month + 1, 1); }
+    return (year + 1, 1, 1);
+}
+
+fn format_timestamp((year, month, day): (usize, usize, usize), hour: usize, minute: usize) -> String {
+    return format!("[{:04}-{:02}-{:02} {:02}:{:02}]", year, month, day, hour, minute);
+}
+
+/// Sorted distinct values of `start..end`, as many as requested (at most the length of the range).
+fn sample_sorted(rng: &mut SyntheticRng, start: usize, end: usize, count: usize) -> Vec<usize> {
+    let mut values : Vec<usize> = (start..end).collect();
+    rng.shuffle(&mut values);
+    values.truncate(count);
+    values.sort();
+    return values;
+}
+
+fn draw_track(canvas: &mut [Vec<char>], x: usize, y: usize, track: char) {
+    let crossed : bool = matches!((canvas[y][x], track), ('-', '|') | ('|', '-'));
+    canvas[y][x] = if crossed { '+' } else { track };
+}
+
+fn draw_loop(canvas: &mut [Vec<char>], (left, top): (usize, usize), (right, bottom): (usize, usize)) {
+    for x in left + 1..right {
+        draw_track(canvas, x, top, '-');
+        draw_track(canvas, x, bottom, '-');
+    }
+    for y in top + 1..bottom {
+        draw_track(canvas, left, y, '|');
+        draw_track(canvas, right, y, '|');
+    }
+    canvas[top][left] = '/';
+    canvas[top][right] = '\\';
+    canvas[bottom][left] = '\\';
+    canvas[bottom][right] = '/';
+}
+
+/// Two crossing loops (a wide one and a tall one meeting at four intersections) with its carts on the top side of the wide loop: a
+/// pair facing each other, which always crash into each other, or a single cart that never meets any other.
+fn cart_cell(rng: &mut SyntheticRng, with_pair: bool) -> Vec<Vec<char>> {
+    let tall_left : usize = 6 + rng.index(3);
+    let tall_right : usize = tall_left + 3 + rng.index(4);
+    let wide_right : usize = tall_right + 3 + rng.index(4);
+    let wide_top : usize = 2 + rng.index(2);
+    let wide_bottom : usize = wide_top + 3 + rng.index(3);
+    let tall_bottom : usize = wide_bottom + 2 + rng.index(2);
+
+    let mut cell : Vec<Vec<char>> = vec![vec![' '; wide_right + 1]; tall_bottom + 1];
+    draw_loop(&mut cell, (0, wide_top), (wide_right, wide_bottom));
+    draw_loop(&mut cell, (tall_left, 0), (tall_right, tall_bottom));
+
+    // Carts keep two straight tracks around them (away from corners and intersections), so that the track under them can be told
+    if with_pair {
+        let right_cart : usize = 4 + rng.index(tall_left - 5);
+        let left_cart : usize = 2 + rng.index(right_cart - 3);
+        cell[wide_top][left_cart] = '>';
+        cell[wide_top][right_cart] = '<';
+    } else {
+        cell[wide_top][2 + rng.index(tall_left - 3)] = if rng.chance(1, 2) { '>' } else { '<' };
+    }
+
+    return cell;
+}
+
+// ======================================================== CHRONAL CALIBRATION ========================================================
+
+/// Frequency changes summing up to zero, so that a repeated frequency is always found.
+pub fn frequency_changes(rng: &mut SyntheticRng, count: usize) -> String {
+    let mut changes : Vec<i64> = (1..count.max(1)).map(|_| rng.range(-50, 51)).collect();
+    changes.push(- changes.iter().sum::<i64>());
+    return changes.iter().map(|change| format!("{:+}", change)).collect::<Vec<String>>().join("\n");
+}
+
+// ==================================================== INVENTORY MANAGEMENT SYSTEM ====================================================
+
+/// Random box IDs of 26 letters, exactly two of them differing by a single letter.
+pub fn box_ids(rng: &mut SyntheticRng, count: usize) -> String {
+    let mut box_ids : Vec<String> = (1..count.max(2)).map(|_| (0..26).map(|_| rng.letter()).collect()).collect();
+    let mut near_duplicate : Vec<char> = box_ids[rng.index(box_ids.len())].chars().collect();
+    let changed_index : usize = rng.index(26);
+    let original_letter : char = near_duplicate[changed_index];
+    while near_duplicate[changed_index] == original_letter { near_duplicate[changed_index] = rng.letter(); }
+
+    box_ids.push(near_duplicate.into_iter().collect());
+    rng.shuffle(&mut box_ids);
+    return box_ids.join("\n");
+}
+
+// ==================================================== NO MATTER HOW YOU SLICE IT =====================================================
+
+/// Random claims over the fabric, one of them set apart from all the others so that it never overlaps.
+pub fn claims(rng: &mut SyntheticRng, count: usize) -> String {
+    let mut areas : Vec<(i64, i64, i64, i64)> = (1..count.max(1))
+        .map(|_| (rng.range(0, 870), rng.range(0, 870), rng.range(1, 30), rng.range(1, 30)))
+        .collect();
+    areas.push((rng.range(905, 960), rng.range(905, 960), rng.range(1, 30), rng.range(1, 30)));
+    rng.shuffle(&mut areas);
+
+    return areas.iter().enumerate()
+        .map(|(index, (left, top, width, height))| format!("#{} @ {},{}: {}x{}", index + 1, left, top, width, height))
+        .collect::<Vec<String>>().join("\n");
+}
+
+// =========================================================== REPOSE RECORD ===========================================================
+
+/// Shuffled guard logs for as many nights, every shift beginning around midnight and followed by up to three naps (the first
+/// night always has one, so that some guard sleeps).
+pub fn guard_logs(rng: &mut SyntheticRng, nights: usize) -> String {
+    let guard_ids : Vec<i64> = (0..(nights / 8).max(2)).map(|_| rng.range(10, 4000)).collect();
+    let mut logs : Vec<String> = Vec::new();
+    let mut eve : (usize, usize, usize) = (1518, 1, 1);
+
+    for night in 0..nights.max(1) {
+        let date : (usize, usize, usize) = next_date(eve);
+        let guard_id : i64 = guard_ids[rng.index(guard_ids.len())];
+        let shift_start : String = match rng.chance(1, 2) {
+            true => format_timestamp(eve, 23, 50 + rng.index(10)),
+            false => format_timestamp(date, 0, rng.index(5)),
+        };
+        logs.push(format!("{} Guard #{} begins shift", shift_start, guard_id));
+
+        let naps : usize = if night == 0 { 1 + rng.index(3) } else { rng.index(4) };
+        let minutes : Vec<usize> = sample_sorted(rng, 5, 60, 2 * naps);
+        for nap in minutes.chunks(2) {
+            logs.push(format!("{} falls asleep", format_timestamp(date, 0, nap[0])));
+            logs.push(format!("{} wakes up", format_timestamp(date, 0, nap[1])));
+        }
+        eve = date;
+    }
+
+    rng.shuffle(&mut logs);
+    return logs.join("\n");
+}
+
+// ======================================================= ALCHEMICAL REDUCTION ========================================================
+
+/// Random polymer mixing every unit type and polarity.
+pub fn polymer(rng: &mut SyntheticRng, length: usize) -> String {
+    return (0..length)
+        .map(|_| if rng.chance(1, 2) { rng.letter() } else { rng.letter().to_ascii_uppercase() })
+        .collect();
+}
+
+// ======================================================== CHRONAL COORDINATES ========================================================
+
+/// Distinct coordinates over an area similar to the puzzle input, including a cross of five whose center always has a finite area.
+pub fn coordinates(rng: &mut SyntheticRng, count: usize) -> String {
+    let mut coordinates : Vec<(i64, i64)> = vec![(200, 200), (200, 100), (200, 300), (100, 200), (300, 200)];
+    let mut seen : HashSet<(i64, i64)> = coordinates.iter().copied().collect();
+    while coordinates.len() < count {
+        let coordinate : (i64, i64) = (rng.range(40, 360), rng.range(40, 360));
+        if seen.insert(coordinate) { coordinates.push(coordinate); }
+    }
+
+    rng.shuffle(&mut coordinates);
+    return coordinates.iter().map(|(x, y)| format!("{}, {}", x, y)).collect::<Vec<String>>().join("\n");
+}
+
+// ======================================================= THE SUM OF ITS PARTS ========================================================
+
+/// Dependencies between as many steps (from 2 to 26), forming an acyclic graph in which every step but the first one has a prerequisite.
+pub fn step_dependencies(rng: &mut SyntheticRng, steps: usize) -> String {
+    let mut order : Vec<char> = ('A'..='Z').collect();
+    rng.shuffle(&mut order);
+    order.truncate(steps.clamp(2, 26));
+
+    let mut dependencies : Vec<String> = Vec::new();
+    for after in 1..order.len() {
+        let required : usize = rng.index(after);
+        for before in 0..after {
+            if before != required && !rng.chance(1, 4) { continue; }
+            dependencies.push(format!("Step {} must be finished before step {} can begin.", order[before], order[after]));
+        }
+    }
+
+    rng.shuffle(&mut dependencies);
+    return dependencies.join("\n");
+}
+
+// ========================================================== MEMORY MANEUVER ==========================================================
+
+/// License tree of as many nodes (each with one to three metadata entries), serialized depth-first on a single line.
+pub fn license_tree(rng: &mut SyntheticRng, nodes: usize) -> String {
+    let nodes : usize = nodes.max(1);
+    let mut children : Vec<Vec<usize>> = vec![Vec::new(); nodes];
+    for node in 1..nodes { children[rng.index(node)].push(node); }
+    let metadata : Vec<Vec<i64>> = (0..nodes).map(|_| (0..rng.range(1, 4)).map(|_| rng.range(1, 10)).collect()).collect();
+
+    // Nodes are entered (header) and left (metadata) from an explicit stack, deep trees must not overflow the call stack
+    let mut numbers : Vec<String> = Vec::new();
+    let mut pending : Vec<(usize, bool)> = vec![(0, false)];
+    while let Some((node, entered)) = pending.pop() {
+        if entered {
+            numbers.extend(metadata[node].iter().map(|entry| entry.to_string()));
+            continue;
+        }
+
+        numbers.push(children[node].len().to_string());
+        numbers.push(metadata[node].len().to_string());
+        pending.push((node, true));
+        pending.extend(children[node].iter().rev().map(|&child| (child, false)));
+    }
+
+    return numbers.join(" ");
+}
+
+// =========================================================== MARBLE MANIA ============================================================
+
+pub fn marble_game(rng: &mut SyntheticRng, last_marble: usize) -> String {
+    return format!("{} players; last marble is worth {} points", rng.range(9, 500), last_marble);
+}
+
+// ========================================================== THE STARS ALIGN ==========================================================
+
+/// Moving points that spell a message of as many letters exactly after `seconds`. Every lit pixel gets several points, coming from
+/// opposite directions, so that the message is the single moment where the points are the closest together.
+pub fn point_cloud(rng: &mut SyntheticRng, letters: usize, seconds: usize) -> String {
+    let message : Vec<usize> = (0..letters.max(1)).map(|_| rng.index(GLYPHS.len())).collect();
+    let lit_pixels : Vec<(i64, i64)> = message.iter().enumerate()
+        .flat_map(|(position, &glyph)| GLYPHS[glyph].1.iter().enumerate()
+            .flat_map(move |(y, row)| row.chars().enumerate()
+                .filter(|&(_, pixel)| pixel == '#')
+                .map(move |(x, _)| ((position * (GLYPH_WIDTH + 1) + x) as i64, y as i64))))
+        .collect();
+
+    // The message is only printed when it is small compared to the number of points
+    let message_size : usize = letters.max(1) * (GLYPH_WIDTH + 1) + GLYPH_HEIGHT + 2;
+    let copies : usize = (4 * message_size).div_ceil(lit_pixels.len()).max(2);
+
+    let (offset_x, offset_y) : (i64, i64) = (rng.range(-100, 100), rng.range(-100, 100));
+    let mut points : Vec<String> = Vec::new();
+    for &(x, y) in lit_pixels.iter() {
+        for copy in 0..copies {
+            let sign : i64 = if copy % 2 == 0 { 1 } else { -1 };
+            let velocity : (i64, i64) = (sign * rng.range(1, 6), sign * rng.range(1, 6));
+            let position : (i64, i64) = (x + offset_x - velocity.0 * seconds as i64, y + offset_y - velocity.1 * seconds as i64);
+            points.push(format!("position=<{:>6}, {:>6}> velocity=<{:>2}, {:>2}>", position.0, position.1, velocity.0, velocity.1));
+        }
+    }
+
+    rng.shuffle(&mut points);
+    return points.join("\n");
+}
+
+// ========================================================== CHRONAL CHARGE ===========================================================
+
+pub fn serial_number(rng: &mut SyntheticRng) -> String {
+    return rng.range(1, 10_000).to_string();
+}
+
+// ==================================================== SUBTERRANEAN SUSTAINABILITY ====================================================
+
+/// Random initial state of as many pots and the 32 rules. Every plant moves by the same offset (or stays) unless its surroundings
+/// make it die, so the number of plants can only go down and the garden always settles into a cycle.
+pub fn plant_rules(rng: &mut SyntheticRng, pots: usize) -> String {
+    let initial_state : String = (0..pots.max(1)).map(|_| if rng.chance(1, 2) { '#' } else { '.' }).collect();
+    let source : usize = rng.index(3) + 1;
+
+    let mut lines : Vec<String> = vec![format!("initial state: {}", initial_state), String::new()];
+    for pattern in 0..32_usize {
+        let pots : Vec<bool> = (0..5).map(|index| pattern & (1 << (4 - index)) != 0).collect();
+        let survives : bool = pots[source] && !rng.chance(1, 4);
+        let pattern_string : String = pots.iter().map(|&plant| if plant { '#' } else { '.' }).collect();
+        lines.push(format!("{} => {}", pattern_string, if survives { '#' } else { '.' }));
+    }
+
+    return lines.join("\n");
+}
+
+// ========================================================= MINE CART MADNESS =========================================================
+
+/// Map of as many separate track networks (at least two), each made of two crossing loops. The carts of the first network are
+/// a single one, and every other network holds a pair bound to crash, so that there is a first crash and a last cart standing.
+pub fn cart_tracks(rng: &mut SyntheticRng, networks: usize) -> String {
+    let cells : Vec<Vec<Vec<char>>> = (0..networks.max(2)).map(|index| cart_cell(rng, index > 0)).collect();
+
+    let mut rows : Vec<String> = Vec::new();
+    for row_cells in cells.chunks(CELLS_PER_ROW) {
+        let row_height : usize = row_cells.iter().map(|cell| cell.len()).max().unwrap();
+        for y in 0..row_height {
+            let line : String = row_cells.iter()
+                .map(|cell| {
+                    let cell_width : usize = cell[0].len();
+                    let cell_line : String = cell.get(y).map(|line| line.iter().collect()).unwrap_or_default();
+                    format!("{:<width$}  ", cell_line, width = cell_width)
+                })
+                .collect();
+            rows.push(line.trim_end().to_owned());
+        }
+        rows.push(String::new());
+    }
+
+    rows.pop();
+    return rows.join("\n");
+}
+
+// ========================================================= CHOCOLATE CHARTS ==========================================================
+
+/// Number of as many digits (not starting with zero), read as a count of recipes and as a sequence of scores.
+pub fn recipe_scores(rng: &mut SyntheticRng, digits: usize) -> String {
+    let first_digit : char = char::from_digit(rng.range(1, 10) as u32, 10).unwrap();
+    return std::iter::once(first_digit)
+        .chain((1..digits.max(1)).map(|_| char::from_digit(rng.range(0, 10) as u32, 10).unwrap()))
+        .collect();
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rolls_dates_over_months_and_years() {
+        assert_eq!(next_date((1518, 2, 28)), (1518, 3, 1));
+        assert_eq!(next_date((1520, 2, 28)), (1520, 2, 29));
+        assert_eq!(next_date((1518, 12, 31)), (1519, 1, 1));
+    }
+
+    #[test]
+    fn builds_near_duplicate_box_ids() {
+        let box_ids : String = box_ids(&mut SyntheticRng::new(3), 50);
+        let box_ids : Vec<&str> = box_ids.lines().collect();
+        let near_duplicates : usize = box_ids.iter().enumerate()
+            .flat_map(|(index, first)| box_ids[index + 1..].iter().map(move |second| (first, second)))
+            .filter(|(first, second)| first.chars().zip(second.chars()).filter(|(a, b)| a != b).count() == 1)
+            .count();
+        assert_eq!((box_ids.len(), near_duplicates), (50, 1));
+    }
+
+    #[test]
+    fn serializes_license_tree_depth_first() {
+        let license_tree : String = license_tree(&mut SyntheticRng::new(3), 1);
+        let numbers : Vec<&str> = license_tree.split(' ').collect();
+        assert_eq!(numbers[0], "0");
+        assert_eq!(numbers.len(), 2 + numbers[1].parse::<usize>().unwrap());
+    }
+
+    #[test]
+    fn draws_crossing_cart_loops() {
+        let cart_tracks : String = cart_tracks(&mut SyntheticRng::new(3), 3);
+        assert_eq!(cart_tracks.matches('+').count(), 12);
+        assert_eq!(cart_tracks.matches(['<', '>']).count(), 5);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,43 @@
+mod inputs;
+mod rng;
+
+pub use inputs::{box_ids, cart_tracks, claims, coordinates, frequency_changes, guard_logs, license_tree, marble_game, plant_rules, point_cloud,
+    polymer, recipe_scores, serial_number, step_dependencies};
+pub use rng::SyntheticRng;
+
+// ======================================================= CONSTANTS DEFINITIONS =======================================================
+
+/// Size of the generated inputs when none is given, close to the one of the puzzle inputs (lines, units, nodes, letters, ...).
+const DEFAULT_SIZES : [usize; 14] = [1_000, 250, 1_300, 300, 50_000, 50, 26, 1_500, 70_000, 8, 0, 100, 10, 6];
+
+// ======================================================== AUXILIARY FUNCTIONS ========================================================
+
+pub fn default_size(day: u8) -> Option<usize> {
+    return DEFAULT_SIZES.get((day as usize).checked_sub(1)?).copied();
+}
+
+/// Random input for the given day, in the exact format of its puzzle input, always the same for the same size and seed. The size
+/// is what the input is made of: changes, box IDs, claims, nights, units, coordinates, steps, nodes, the last marble, letters of the
+/// message, pots, track networks or digits (the serial number of day 11 has no size).
+pub fn generate_input(day: u8, size: usize, seed: u64) -> Option<String> {
+    let mut rng : SyntheticRng = SyntheticRng::new(seed);
+    let input : String = match day {
+        1 => frequency_changes(&mut rng, size),
+        2 => box_ids(&mut rng, size),
+        3 => claims(&mut rng, size),
+        4 => guard_logs(&mut rng, size),
+        5 => polymer(&mut rng, size),
+        6 => coordinates(&mut rng, size),
+        7 => step_dependencies(&mut rng, size),
+        8 => license_tree(&mut rng, size),
+        9 => marble_game(&mut rng, size),
+        10 => { let seconds : usize = rng.range(1_000, 20_000) as usize; point_cloud(&mut rng, size, seconds) },
+        11 => serial_number(&mut rng),
+        12 => plant_rules(&mut rng, size),
+        13 => cart_tracks(&mut rng, size),
+        14 => recipe_scores(&mut rng, size),
+        _ => return None,
+    };
+
+    return Some(input);
+}
\ No newline at end of file
@@ -0,0 +1,73 @@
+// ======================================================== STRUCTS DEFINITIONS ========================================================
+
+/// Deterministic xorshift generator, so that the same seed always builds the very same input.
+#[derive(Clone, Debug)]
+pub struct SyntheticRng {
+    state: u64,
+}
+
+// ====================================================== STRUCTS IMPLEMENTATIONS ======================================================
+
+impl SyntheticRng {
+    /// Generator for the given seed (a zero seed, which would only ever yield zeros, is replaced by a fixed one).
+    pub fn new(seed: u64) -> SyntheticRng {
+        SyntheticRng { state: if seed == 0 { 0x2018_1201 } else { seed } }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        return self.state;
+    }
+
+    /// Value in `start..end` (which must not be empty).
+    pub fn range(&mut self, start: i64, end: i64) -> i64 {
+        return start + (self.next_u64() % (end - start) as u64) as i64;
+    }
+
+    pub fn index(&mut self, length: usize) -> usize {
+        return self.range(0, length as i64) as usize;
+    }
+
+    /// `true` with a probability of `numerator / denominator`.
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        return self.next_u64() % denominator < numerator;
+    }
+
+    pub fn letter(&mut self) -> char {
+        return (b'a' + self.range(0, 26) as u8) as char;
+    }
+
+    pub fn shuffle<T>(&mut self, values: &mut [T]) {
+        for index in (1..values.len()).rev() {
+            values.swap(index, self.index(index + 1));
+        }
+    }
+}
+
+// =============================================================== TESTS ===============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeats_sequences_for_the_same_seed() {
+        let mut first : SyntheticRng = SyntheticRng::new(7);
+        let mut second : SyntheticRng = SyntheticRng::new(7);
+        assert_eq!((0..10).map(|_| first.next_u64()).collect::<Vec<u64>>(), (0..10).map(|_| second.next_u64()).collect::<Vec<u64>>());
+        assert_ne!(SyntheticRng::new(0).next_u64(), 0);
+    }
+
+    #[test]
+    fn keeps_values_in_range() {
+        let mut rng : SyntheticRng = SyntheticRng::new(7);
+        assert!((0..1000).map(|_| rng.range(-3, 4)).all(|value| (-3..4).contains(&value)));
+
+        let mut values : Vec<usize> = (0..20).collect();
+        rng.shuffle(&mut values);
+        values.sort();
+        assert_eq!(values, (0..20).collect::<Vec<usize>>());
+    }
+}
\ No newline at end of file
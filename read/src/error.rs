@@ -0,0 +1,66 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+// ======================================================== STRUCTS DEFINITIONS ========================================================
+
+pub type ReadResult<T> = Result<T, ReadError>;
+
+#[derive(Debug)]
+pub enum ReadErrorKind {
+    Io(io::Error),
+    MissingLine,
+    InvalidValue { value: String, reason: String },
+    InvalidDigit(char),
+}
+
+/// Error raised while reading an input, located by source name and by 1-based line and column when they are known.
+#[derive(Debug)]
+pub struct ReadError {
+    pub source_name: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub kind: ReadErrorKind,
+}
+
+// ====================================================== STRUCTS IMPLEMENTATIONS ======================================================
+
+impl ReadError {
+    pub fn new(source_name: &str, kind: ReadErrorKind) -> ReadError {
+        ReadError { source_name: source_name.to_owned(), line: None, column: None, kind: kind }
+    }
+
+    pub fn at(source_name: &str, line: usize, column: usize, kind: ReadErrorKind) -> ReadError {
+        ReadError { source_name: source_name.to_owned(), line: Some(line), column: Some(column), kind: kind }
+    }
+}
+
+impl fmt::Display for ReadErrorKind {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadErrorKind::Io(error) => write!(formatter, "{}", error),
+            ReadErrorKind::MissingLine => write!(formatter, "missing line"),
+            ReadErrorKind::InvalidValue { value, reason } => write!(formatter, "invalid value '{}' ({})", value, reason),
+            ReadErrorKind::InvalidDigit(character) => write!(formatter, "invalid digit '{}'", character),
+        }
+    }
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(formatter, "{}:{}:{}: {}", self.source_name, line, column, self.kind),
+            (Some(line), None) => write!(formatter, "{}:{}: {}", self.source_name, line, self.kind),
+            _ => write!(formatter, "{}: {}", self.source_name, self.kind),
+        }
+    }
+}
+
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            ReadErrorKind::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,132 @@
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::error::{ReadError, ReadErrorKind, ReadResult};
+
+// ======================================================== STRUCTS DEFINITIONS ========================================================
+
+/// Whole text of a puzzle input, together with the name of where it was read from (used to locate errors).
+#[derive(Clone, Debug)]
+pub struct Input {
+    name: String,
+    text: String,
+}
+
+// ====================================================== STRUCTS IMPLEMENTATIONS ======================================================
+
+impl Input {
+    pub fn from_path<P: AsRef<Path>>(path: P) -> ReadResult<Input> {
+        let name : String = path.as_ref().display().to_string();
+        let text : String = fs::read_to_string(path.as_ref())
+            .map_err(|error| ReadError::new(&name, ReadErrorKind::Io(error)))?;
+
+        return Ok(Input { name: name, text: text });
+    }
+
+    pub fn from_stdin() -> ReadResult<Input> {
+        let name : String = "<stdin>".to_owned();
+        let mut text : String = String::new();
+        io::stdin().read_to_string(&mut text)
+            .map_err(|error| ReadError::new(&name, ReadErrorKind::Io(error)))?;
+
+        return Ok(Input { name: name, text: text });
+    }
+
+    pub fn from_string(name: &str, text: &str) -> Input {
+        Input { name: name.to_owned(), text: text.to_owned() }
+    }
+
+    pub fn get_name(&self) -> &str { &self.name }
+    pub fn get_text(&self) -> &str { &self.text }
+
+    pub fn error_at(&self, line_index: usize, column_index: usize, kind: ReadErrorKind) -> ReadError {
+        ReadError::at(&self.name, line_index + 1, column_index + 1, kind)
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        return self.text.lines()
+            .map(|line| line.to_owned())
+            .collect();
+    }
+
+    pub fn first_line(&self) -> ReadResult<&str> {
+        return self.text.lines().next()
+            .ok_or_else(|| ReadError::at(&self.name, 1, 1, ReadErrorKind::MissingLine));
+    }
+
+    pub fn parse_lines<T>(&self) -> ReadResult<Vec<T>> where T: FromStr, T::Err: Display {
+        return self.text.lines().enumerate()
+            .map(|(line_index, line)| self.parse_value(line_index, 0, line))
+            .collect();
+    }
+
+    pub fn parse_separated_lines<T>(&self, separator: &str) -> ReadResult<Vec<Vec<T>>> where T: FromStr, T::Err: Display {
+        return self.text.lines().enumerate()
+            .map(|(line_index, line)| {
+                let mut column_index : usize = 0;
+                line.split(separator)
+                    .map(|value| {
+                        let parsed_value : ReadResult<T> = self.parse_value(line_index, column_index, value);
+                        column_index += value.chars().count() + separator.chars().count();
+                        parsed_value })
+                    .collect::<ReadResult<Vec<T>>>() })
+            .collect();
+    }
+
+    pub fn int_lines(&self) -> ReadResult<Vec<i64>> {
+        return self.parse_lines();
+    }
+
+    pub fn list_int_lines(&self, separator: &str) -> ReadResult<Vec<Vec<i64>>> {
+        return self.parse_separated_lines(separator);
+    }
+
+    pub fn digits(&self) -> ReadResult<Vec<Vec<u32>>> {
+        return self.text.lines().enumerate()
+            .map(|(line_index, line)| line.chars().enumerate()
+                .map(|(column_index, character)| character.to_digit(10)
+                    .ok_or_else(|| self.error_at(line_index, column_index, ReadErrorKind::InvalidDigit(character))))
+                .collect::<ReadResult<Vec<u32>>>())
+            .collect();
+    }
+
+    pub fn chars(&self) -> Vec<Vec<char>> {
+        return self.text.lines()
+            .map(|line| line.chars().collect())
+            .collect();
+    }
+
+    fn parse_value<T>(&self, line_index: usize, column_index: usize, value: &str) -> ReadResult<T> where T: FromStr, T::Err: Display {
+        return value.parse::<T>()
+            .map_err(|error| self.error_at(line_index, column_index, ReadErrorKind::InvalidValue { value: value.to_owned(), reason: error.to_string() }));
+    }
+}
+
+// ======================================================== AUXILIARY FUNCTIONS ========================================================
+
+pub fn read_string<P: AsRef<Path>>(path: P) -> ReadResult<String> {
+    return Ok(Input::from_path(path)?.text);
+}
+
+pub fn read_lines<P: AsRef<Path>>(path: P) -> ReadResult<Vec<String>> {
+    return Ok(Input::from_path(path)?.lines());
+}
+
+pub fn read_int_lines<P: AsRef<Path>>(path: P) -> ReadResult<Vec<i64>> {
+    return Input::from_path(path)?.int_lines();
+}
+
+pub fn read_list_int_lines<P: AsRef<Path>>(path: P, separator: &str) -> ReadResult<Vec<Vec<i64>>> {
+    return Input::from_path(path)?.list_int_lines(separator);
+}
+
+pub fn read_digits<P: AsRef<Path>>(path: P) -> ReadResult<Vec<Vec<u32>>> {
+    return Input::from_path(path)?.digits();
+}
+
+pub fn read_chars<P: AsRef<Path>>(path: P) -> ReadResult<Vec<Vec<char>>> {
+    return Ok(Input::from_path(path)?.chars());
+}
\ No newline at end of file
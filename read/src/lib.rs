@@ -0,0 +1,5 @@
+mod error;
+mod input;
+
+pub use error::{ReadError, ReadErrorKind, ReadResult};
+pub use input::{Input, read_string, read_lines, read_int_lines, read_list_int_lines, read_digits, read_chars};
\ No newline at end of file